@@ -48,6 +48,23 @@ pub fn pretty_print_search_results(results: &Output) -> String {
         results.total_hit_count
     ));
 
+    if let Some(facets) = &results.facets {
+        for (key, counts) in facets {
+            let counts: Vec<String> = counts
+                .iter()
+                .map(|(value, count)| format!("{value} ({count})"))
+                .collect();
+            output.push_str(&format!("\n{}: {}", key.bold(), counts.join(", ")));
+        }
+    }
+
+    if !results.suggestions.is_empty() {
+        output.push_str(&format!(
+            "\nDid you mean: {}?",
+            results.suggestions.join(", ").bold()
+        ));
+    }
+
     output
 }
 
@@ -87,6 +104,8 @@ mod tests {
             }],
             total_hit_count: 21,
             url_prefix: String::new(),
+            facets: None,
+            suggestions: vec![],
         };
 
         assert_eq!(
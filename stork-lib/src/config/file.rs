@@ -1,6 +1,6 @@
 #![allow(clippy::upper_case_acronyms)]
 
-use super::{FrontmatterConfig, StemmingConfig};
+use super::{FrontmatterConfig, RecordMapping, StemmingConfig, Tokenizer};
 use core::fmt;
 use serde::{Deserialize, Serialize};
 use smart_default::SmartDefault;
@@ -11,7 +11,14 @@ type Fields = HashMap<String, String>;
 #[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct File {
+    /// If empty, the title is taken from the document's frontmatter, its
+    /// `<title>` or first `<h1>`, or its first Markdown heading.
+    #[serde(default)]
     pub title: String,
+
+    /// If empty, files with a `path` get a URL built from the input config's
+    /// `url_template`.
+    #[serde(default)]
     pub url: String,
 
     /// Implicit source will take from the destination URL
@@ -23,6 +30,9 @@ pub struct File {
     #[serde(default)]
     pub stemming_override: Option<StemmingConfig>,
 
+    #[serde(default)]
+    pub tokenizer_override: Option<Tokenizer>,
+
     #[serde(default)]
     pub html_selector_override: Option<String>,
 
@@ -35,6 +45,16 @@ pub struct File {
     #[serde(default)]
     pub filetype: Option<Filetype>,
 
+    /// Multiplies this file's search result score, so that important pages
+    /// can outrank others that happen to mention the same words. Defaults to 1.
+    #[serde(default)]
+    pub boost: Option<f32>,
+
+    /// For a `json` or `csv` source, which keys or columns of each record
+    /// hold the entry's title, URL, contents, filetype, and fields.
+    #[serde(default)]
+    pub mapping: Option<RecordMapping>,
+
     #[serde(flatten, default)]
     pub fields: Fields,
 }
@@ -54,7 +74,11 @@ impl fmt::Display for File {
             f,
             "{}",
             match &self.source() {
-                DataSource::FilePath(path) => path,
+                DataSource::FilePath(path)
+                | DataSource::Sitemap(path)
+                | DataSource::Crawl(path)
+                | DataSource::JSON(path)
+                | DataSource::CSV(path) => path,
                 DataSource::URL(url) => url,
 
                 // if there's no string representation of where to find the file,
@@ -77,6 +101,37 @@ pub enum DataSource {
 
     #[serde(rename = "path")]
     FilePath(String),
+
+    /// A local or remote `sitemap.xml`, or sitemap index. When the index is
+    /// built, this becomes one file for each page in the sitemap, with the
+    /// page's `lastmod` as a field.
+    #[serde(rename = "sitemap")]
+    Sitemap(String),
+
+    /// A local directory of built HTML, like a static site generator's
+    /// `public/` directory. When the index is built, this becomes one file
+    /// for each page that can be reached by following relative links from the
+    /// directory's `index.html`. If this is an HTML file instead, the crawl
+    /// starts there, and the site's root is the outermost directory above the
+    /// file that has an `index.html`.
+    #[serde(rename = "crawl")]
+    Crawl(String),
+
+    /// A local or remote file of JSON records, either as an array or as one
+    /// record per line (NDJSON). When the index is built, each record becomes
+    /// a file, using the file's `mapping` to find its title, URL, contents,
+    /// filetype, and fields.
+    #[serde(rename = "json")]
+    #[allow(clippy::upper_case_acronyms)]
+    JSON(String),
+
+    /// A local or remote CSV file with a header row, or a TSV file if the
+    /// name ends in `.tsv`. When the index is built, each row becomes a file,
+    /// using the file's `mapping` to find its title, URL, contents, and
+    /// filetype. The row's other columns become fields.
+    #[serde(rename = "csv")]
+    #[allow(clippy::upper_case_acronyms)]
+    CSV(String),
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
@@ -85,6 +140,9 @@ pub enum Filetype {
     SRTSubtitle,
     HTML,
     Markdown,
+    WebVTT,
+    AsciiDoc,
+    ReStructuredText,
 }
 
 #[cfg(test)]
@@ -95,21 +153,28 @@ mod tests {
     use toml::de::Error;
 
     #[test]
-    fn file_with_only_title_fails() {
+    fn file_with_only_title_has_no_source() {
         let toml = r#"title = "Derp""#;
-        let error: Error = toml::from_str::<File>(toml).unwrap_err();
-        let computed = error.to_string();
-        let expected = "missing field `url` at line 1 column 1";
-        assert_eq!(computed, expected);
+        let file: File = toml::from_str(toml).unwrap();
+        assert_eq!(file.explicit_source, None);
+        assert_eq!(file.url, "");
     }
 
     #[test]
-    fn json_file_with_only_title_fails() {
+    fn json_file_with_only_title_has_no_source() {
         let json = r#"{"title": "Derp"}"#;
-        let error: JsonError = serde_json::from_str::<File>(json).unwrap_err();
-        let computed = error.to_string();
-        let expected = "missing field `url` at line 1 column 17";
-        assert_eq!(computed, expected);
+        let file: File = serde_json::from_str(json).unwrap();
+        assert_eq!(file.explicit_source, None);
+        assert_eq!(file.url, "");
+    }
+
+    #[test]
+    fn file_with_only_path_has_no_title_or_url() {
+        let toml = r#"path = "posts/hello.md""#;
+        let file: File = toml::from_str(toml).unwrap();
+        assert_eq!(file.source(), DataSource::FilePath("posts/hello.md".into()));
+        assert_eq!(file.title, "");
+        assert_eq!(file.url, "");
     }
 
     #[test]
@@ -157,22 +222,48 @@ mod tests {
     }
 
     #[test]
-    fn file_with_only_src_url_fails() {
+    fn file_with_only_src_url_has_empty_url() {
         let toml = r#"title = "Derp"
         src_url = "google.com""#;
-        let error: Error = toml::from_str::<File>(toml).unwrap_err();
-        let computed = error.to_string();
-        let expected = "missing field `url` at line 1 column 1";
-        assert_eq!(computed, expected);
+        let file: File = toml::from_str(toml).unwrap();
+        assert_eq!(file.source(), DataSource::URL("google.com".into()));
+        assert_eq!(file.url, "");
     }
 
     #[test]
-    fn json_file_with_only_src_url_fails() {
+    fn json_file_with_only_src_url_has_empty_url() {
         let json = r#"{"title": "Derp", "src_url": "google.com"}"#;
-        let error: JsonError = serde_json::from_str::<File>(json).unwrap_err();
-        let computed = error.to_string();
-        let expected = "missing field `url` at line 1 column 42";
-        assert_eq!(computed, expected);
+        let file: File = serde_json::from_str(json).unwrap();
+        assert_eq!(file.source(), DataSource::URL("google.com".into()));
+        assert_eq!(file.url, "");
+    }
+
+    #[test]
+    fn file_with_sitemap_source() {
+        let toml = r#"sitemap = "https://example.com/sitemap.xml""#;
+        let file: File = toml::from_str(toml).unwrap();
+        assert_eq!(
+            file.source(),
+            DataSource::Sitemap("https://example.com/sitemap.xml".into())
+        );
+        assert_eq!(file.to_string(), "https://example.com/sitemap.xml");
+    }
+
+    #[test]
+    fn file_with_crawl_source() {
+        let toml = r#"crawl = "public""#;
+        let file: File = toml::from_str(toml).unwrap();
+        assert_eq!(file.source(), DataSource::Crawl("public".into()));
+        assert_eq!(file.to_string(), "public");
+    }
+
+    #[test]
+    fn file_with_csv_source_and_mapping() {
+        let toml = r#"csv = "faq.tsv"
+        mapping = { title = "Question", contents = "Answer" }"#;
+        let file: File = toml::from_str(toml).unwrap();
+        assert_eq!(file.source(), DataSource::CSV("faq.tsv".into()));
+        assert_eq!(file.mapping.unwrap().contents, "Answer");
     }
 
     #[test]
@@ -218,4 +309,14 @@ mod tests {
         let expected = "unknown field `src_url` at line 3 column 32";
         assert_eq!(computed, expected);
     }
+
+    #[test]
+    fn file_with_boost() {
+        let toml = r#"title = "Derp"
+        url = "blorp"
+        boost = 2"#;
+        let file: File = toml::from_str(toml).unwrap();
+        assert_eq!(file.boost, Some(2.0));
+        assert!(file.fields.is_empty());
+    }
 }
@@ -3,6 +3,15 @@
 use serde::{Deserialize, Serialize};
 use smart_default::SmartDefault;
 
+/**
+ * What to do with YAML frontmatter at the top of a file.
+ *
+ * `Ignore` indexes the frontmatter as if it were part of the contents, and
+ * `Omit` leaves it out. `Parse` also leaves it out, but adds its values to
+ * the entry's fields, with nested values flattened into dotted keys like
+ * `author.name`. Fields set in the config take precedence over fields from
+ * the frontmatter.
+ */
 #[derive(Serialize, Deserialize, Debug, Clone, SmartDefault, PartialEq)]
 pub enum FrontmatterConfig {
     Ignore,
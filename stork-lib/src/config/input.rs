@@ -2,8 +2,11 @@
 
 use serde::{Deserialize, Serialize};
 use smart_default::SmartDefault;
+use std::collections::BTreeMap;
 
-use super::{File, FrontmatterConfig, SRTConfig, StemmingConfig};
+use super::{
+    File, FrontmatterConfig, SRTConfig, SourceConfig, StemmingConfig, StopwordsConfig, Synonyms,
+};
 
 #[derive(Serialize, Deserialize, Clone, Debug, SmartDefault, PartialEq)]
 #[serde(deny_unknown_fields)]
@@ -15,6 +18,41 @@ pub enum TitleBoost {
     Ridiculous,
 }
 
+/**
+ * How words are normalized before they're indexed and before they're
+ * searched for. Words are always lowercased.
+ *
+ * `NFKC` applies Unicode compatibility normalization, so that (for example)
+ * full-width and half-width characters match each other. `FoldDiacritics`
+ * also removes accents, so that `café` and `cafe` match each other.
+ */
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, SmartDefault, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub enum Normalization {
+    #[default]
+    None,
+    #[serde(rename = "NFKC")]
+    Nfkc,
+    FoldDiacritics,
+}
+
+/**
+ * How text is split into words, both when building the index and when
+ * searching it.
+ *
+ * `Standard` splits on whitespace and hyphens. `CJKBigram` also splits
+ * Chinese, Japanese, and Korean text, which usually isn't written with spaces
+ * between words, into one word per character, and indexes each pair of
+ * adjacent characters so that no dictionary of words is needed.
+ */
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, SmartDefault, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub enum Tokenizer {
+    #[default]
+    Standard,
+    CJKBigram,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, SmartDefault, PartialEq)]
 #[serde(deny_unknown_fields, default)]
 #[allow(non_snake_case)]
@@ -23,6 +61,12 @@ pub struct InputConfig {
     pub UNUSED_surrounding_word_count: Option<u8>,
     pub base_directory: String,
     pub url_prefix: String,
+
+    /// How URLs are built for files that have a `path` but no `url`. The
+    /// template can use `{path}`, `{dir}`, `{stem}`, and `{ext}`; for
+    /// example, `/{dir}/{stem}/` turns `posts/hello.md` into `/posts/hello/`.
+    #[default("{path}".to_string())]
+    pub url_template: String,
     pub title_boost: TitleBoost,
     pub stemming: StemmingConfig,
     pub html_selector: Option<String>,
@@ -32,6 +76,9 @@ pub struct InputConfig {
     pub frontmatter_handling: FrontmatterConfig,
     pub files: Vec<File>,
 
+    /// Directories to walk for more files to index, alongside `files`.
+    pub sources: Vec<SourceConfig>,
+
     #[default = false]
     pub break_on_file_error: bool,
     pub srt_config: SRTConfig,
@@ -41,4 +88,34 @@ pub struct InputConfig {
 
     #[default = 1]
     pub minimum_index_ideographic_substring_length: u8,
+
+    #[default(Normalization::Nfkc)]
+    pub normalization: Normalization,
+
+    pub tokenizer: Tokenizer,
+
+    /// Defaults to the stopwords of the stemming language, or to English's
+    /// if words aren't stemmed.
+    #[default(None)]
+    pub stopwords: Option<StopwordsConfig>,
+
+    /// Leaves stopwords out of the index entirely, making it smaller. Searches
+    /// for a stopword will find nothing.
+    #[default = false]
+    pub remove_stopwords: bool,
+
+    /// Words that should also match other words, e.g. `k8s = "kubernetes"`.
+    /// A word and its synonyms all match each other, in every direction.
+    pub synonyms: BTreeMap<String, Synonyms>,
+
+    /// A file of more synonyms, relative to the base directory. Each line
+    /// looks like `k8s = kubernetes` or `js = javascript, ecmascript`, and
+    /// lines starting with `#` are ignored.
+    #[default(None)]
+    pub synonyms_file: Option<String>,
+
+    /// The score a match through a synonym gets. For reference, an exact
+    /// match scores 128 and a match on a word with the same stem scores 64.
+    #[default = 96]
+    pub synonym_score: u8,
 }
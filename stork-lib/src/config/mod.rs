@@ -4,20 +4,32 @@ use serde::{Deserialize, Serialize};
 use smart_default::SmartDefault;
 
 mod input;
-pub use input::{InputConfig, TitleBoost};
+pub use input::{InputConfig, Normalization, TitleBoost, Tokenizer};
 
 mod output;
-pub use output::OutputConfig;
+pub use output::{FieldType, OutputConfig, Ranking};
 
 mod stemming;
 pub use stemming::StemmingConfig;
 
+mod stopwords;
+pub use stopwords::StopwordsConfig;
+
+mod synonyms;
+pub use synonyms::Synonyms;
+
 mod frontmatter;
 pub use self::frontmatter::FrontmatterConfig;
 
 mod file;
 pub use file::{DataSource, File, Filetype};
 
+mod source;
+pub use source::SourceConfig;
+
+mod record_mapping;
+pub use record_mapping::RecordMapping;
+
 mod srt;
 pub use srt::{SRTConfig, SRTTimestampFormat};
 
@@ -68,7 +80,7 @@ impl TryFrom<&str> for Config {
 
 #[cfg(test)]
 mod tests {
-    use std::collections::HashMap;
+    use std::collections::{BTreeMap, HashMap};
 
     use super::*;
     use pretty_assertions::assert_eq;
@@ -80,12 +92,14 @@ mod tests {
         assert_eq!(error, ConfigReadError::EmptyString);
     }
 
+    #[allow(clippy::too_many_lines)]
     fn get_default_config() -> Config {
         Config {
             input: InputConfig {
                 UNUSED_surrounding_word_count: None,
                 base_directory: "test/federalist".into(),
                 url_prefix: String::new(),
+                url_template: "{path}".into(),
                 title_boost: TitleBoost::Moderate,
                 stemming: StemmingConfig::Language(
                     rust_stemmers::Algorithm::English,
@@ -104,10 +118,13 @@ mod tests {
                         ),
                         id: None,
                         stemming_override: None,
+                        tokenizer_override: None,
                         html_selector_override: None,
                         exclude_html_selector_override: None,
                         frontmatter_handling_override: None,
                         filetype: None,
+                        boost: None,
+                        mapping: None,
                         fields: HashMap::new(),
                     },
                     File {
@@ -120,10 +137,13 @@ mod tests {
                         ),
                         id: None,
                         stemming_override: None,
+                        tokenizer_override: None,
                         html_selector_override: None,
                         exclude_html_selector_override: None,
                         frontmatter_handling_override: None,
                         filetype: None,
+                        boost: None,
+                        mapping: None,
                         fields: HashMap::new(),
                     },
                     File {
@@ -136,13 +156,17 @@ mod tests {
                         ),
                         id: None,
                         stemming_override: None,
+                        tokenizer_override: None,
                         html_selector_override: None,
                         exclude_html_selector_override: None,
                         frontmatter_handling_override: None,
                         filetype: None,
+                        boost: None,
+                        mapping: None,
                         fields: HashMap::new(),
                     },
                 ],
+                sources: vec![],
                 break_on_file_error: false,
                 srt_config: SRTConfig {
                     timestamp_linking: true,
@@ -151,6 +175,13 @@ mod tests {
                 },
                 minimum_indexed_substring_length: 3,
                 minimum_index_ideographic_substring_length: 1,
+                normalization: Normalization::Nfkc,
+                tokenizer: Tokenizer::Standard,
+                stopwords: None,
+                remove_stopwords: false,
+                synonyms: BTreeMap::new(),
+                synonyms_file: None,
+                synonym_score: 96,
             },
             output: OutputConfig {
                 UNUSED_filename: None,
@@ -159,6 +190,9 @@ mod tests {
                 excerpt_buffer: 8,
                 excerpts_per_result: 5,
                 displayed_results_count: 10,
+                facets: vec![],
+                ranking: Ranking::Legacy,
+                field_types: BTreeMap::new(),
             },
         }
     }
@@ -241,14 +275,24 @@ debug = true
     }
 
     #[test]
-    fn empty_file_array_fails() {
+    fn empty_file_parses_without_a_source() {
+        // Files without a source fail when the index is built, rather than
+        // when the config is parsed, since titles and URLs can be derived.
         let contents = r#"
 [input]
 files = [{}]
     "#;
-        let result: toml::de::Error = toml::from_str::<Config>(contents).unwrap_err();
-        let computed = result.to_string();
-        let expected = "missing field `title` for key `input.files` at line 3 column 10"; // TODO: Can this be nicer?
-        assert_eq!(computed, expected);
+        let config = toml::from_str::<Config>(contents).unwrap();
+        assert_eq!(config.input.files, vec![File::default()]);
+    }
+
+    #[test]
+    fn normalization_is_written_as_nfkc() {
+        let contents = r#"
+[input]
+normalization = "NFKC"
+    "#;
+        let config = toml::from_str::<Config>(contents).unwrap();
+        assert_eq!(config.input.normalization, Normalization::Nfkc);
     }
 }
@@ -1,5 +1,40 @@
 use serde::{Deserialize, Serialize};
 use smart_default::SmartDefault;
+use std::collections::BTreeMap;
+
+/**
+ * How search results are ordered.
+ *
+ * `Legacy` scores results by how closely the query words appear together in
+ * the entry. `BM25` also weighs each word by how rare it is across the index
+ * and how long the entry is, so that common words don't drown out meaningful
+ * ones. BM25 requires extra statistics to be stored in the index.
+ */
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, SmartDefault, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub enum Ranking {
+    #[default]
+    Legacy,
+    BM25,
+}
+
+/**
+ * How a field's values are compared when results are filtered by a range of
+ * values or sorted by the field.
+ *
+ * `Keyword` values are compared as case-insensitive text. `Number` values
+ * are parsed as decimal numbers. `Date` values are parsed as ISO 8601 dates,
+ * like `2024-01-01` or `2024-01-01T09:30:00Z`. Values that can't be parsed
+ * are treated as missing.
+ */
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, SmartDefault, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub enum FieldType {
+    #[default]
+    Keyword,
+    Number,
+    Date,
+}
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, SmartDefault)]
 #[serde(deny_unknown_fields, default)]
@@ -23,4 +58,14 @@ pub struct OutputConfig {
 
     #[default = 10]
     pub displayed_results_count: u8,
+
+    /// Field keys whose values should be counted across all search results,
+    /// so that a UI can display how many results fall into each category.
+    pub facets: Vec<String>,
+
+    pub ranking: Ranking,
+
+    /// The types of fields that results can be filtered by ranges of or
+    /// sorted by. Fields that aren't listed are compared as keywords.
+    pub field_types: BTreeMap<String, FieldType>,
 }
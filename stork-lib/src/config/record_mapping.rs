@@ -0,0 +1,59 @@
+use serde::{Deserialize, Serialize};
+use smart_default::SmartDefault;
+use std::collections::BTreeMap;
+
+/**
+ * Says which keys of a record in a JSON feed, or which columns of a row in a
+ * CSV file, hold each part of an entry. JSON keys can be dotted to reach into
+ * nested objects, like `author.name`.
+ */
+#[derive(Serialize, Deserialize, Debug, Clone, SmartDefault, PartialEq)]
+#[serde(deny_unknown_fields, default)]
+pub struct RecordMapping {
+    #[default("title".to_string())]
+    pub title: String,
+
+    #[default("url".to_string())]
+    pub url: String,
+
+    #[default("contents".to_string())]
+    pub contents: String,
+
+    /// The record's value should be a filetype like `HTML` or `Markdown`.
+    /// Records without one are treated as plain text.
+    #[default("filetype".to_string())]
+    pub filetype: String,
+
+    /// Maps each field name to the key that holds its value, like
+    /// `date = "published_at"`. For CSV files, columns that aren't mapped to
+    /// anything also become fields, named after their header.
+    pub fields: BTreeMap<String, String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::File;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn mapping_deserializes_with_defaults() {
+        let file: File = toml::from_str(
+            r#"
+json = "export.ndjson"
+mapping = { title = "headline", fields = { date = "published_at" } }
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            file.mapping,
+            Some(RecordMapping {
+                title: "headline".to_string(),
+                fields: BTreeMap::from([("date".to_string(), "published_at".to_string())]),
+                ..RecordMapping::default()
+            })
+        );
+        assert_eq!(file.mapping.unwrap().contents, "contents");
+    }
+}
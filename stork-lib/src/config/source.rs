@@ -0,0 +1,82 @@
+use serde::{Deserialize, Serialize};
+use smart_default::SmartDefault;
+
+use super::{Filetype, FrontmatterConfig, StemmingConfig, Tokenizer};
+
+/**
+ * A set of files to index, found by walking a directory when the index is
+ * built. Each file that's found becomes a `File` with the overrides below;
+ * its title and URL are derived from the document and its path.
+ *
+ * `include` and `exclude` are glob patterns matched against each file's path
+ * relative to `directory`, using `/` as the separator. A single `*` doesn't
+ * match across directories, but `**` does, so it can be used to match files
+ * at any depth.
+ */
+#[derive(Serialize, Deserialize, Debug, Clone, SmartDefault, PartialEq)]
+#[serde(deny_unknown_fields, default)]
+pub struct SourceConfig {
+    /// The directory to walk, relative to the base directory.
+    pub directory: String,
+
+    /// Files have to match one of these patterns to be indexed.
+    #[default(vec!["**/*".to_string()])]
+    pub include: Vec<String>,
+
+    /// Files that match any of these patterns aren't indexed.
+    pub exclude: Vec<String>,
+
+    /// The filetype of every matching file. If not set, the filetype is
+    /// determined from each file's extension, and files with unknown
+    /// extensions are skipped.
+    pub filetype: Option<Filetype>,
+
+    pub stemming_override: Option<StemmingConfig>,
+    pub tokenizer_override: Option<Tokenizer>,
+    pub html_selector_override: Option<String>,
+    pub exclude_html_selector_override: Option<String>,
+    pub frontmatter_handling_override: Option<FrontmatterConfig>,
+    pub boost: Option<f32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn sources_deserialize_with_defaults() {
+        let config: Config = toml::from_str(
+            r#"
+[[input.sources]]
+directory = "content"
+exclude = ["drafts/**"]
+stemming_override = "none"
+
+[[input.sources]]
+include = ["**/*.html"]
+html_selector_override = "main"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.input.sources,
+            vec![
+                SourceConfig {
+                    directory: "content".to_string(),
+                    exclude: vec!["drafts/**".to_string()],
+                    stemming_override: Some(StemmingConfig::None),
+                    ..SourceConfig::default()
+                },
+                SourceConfig {
+                    include: vec!["**/*.html".to_string()],
+                    html_selector_override: Some("main".to_string()),
+                    ..SourceConfig::default()
+                }
+            ]
+        );
+        assert_eq!(config.input.sources[0].include, vec!["**/*".to_string()]);
+    }
+}
@@ -0,0 +1,128 @@
+use rust_stemmers::Algorithm;
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+
+use super::StemmingConfig;
+
+/**
+ * Which words are treated as stopwords: very common words that are given a
+ * low score when searched for, and that can be left out of the index
+ * entirely with `remove_stopwords`.
+ *
+ * In a config file, this is either `"none"`, the name of a language (spelled
+ * the same way as in the stemming config, e.g. `"Dutch"`), or a list of
+ * words.
+ */
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(into = "StopwordsConfigValue")]
+#[serde(try_from = "StopwordsConfigValue")]
+pub enum StopwordsConfig {
+    None,
+    Language(Algorithm),
+    Custom(Vec<String>),
+}
+
+impl Default for StopwordsConfig {
+    fn default() -> Self {
+        StopwordsConfig::Language(Algorithm::English)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum StopwordsConfigValue {
+    Name(String),
+    List(Vec<String>),
+}
+
+impl TryFrom<StopwordsConfigValue> for StopwordsConfig {
+    type Error = String;
+
+    fn try_from(value: StopwordsConfigValue) -> Result<Self, Self::Error> {
+        match value {
+            StopwordsConfigValue::List(words) => Ok(StopwordsConfig::Custom(words)),
+            StopwordsConfigValue::Name(name) => match StemmingConfig::try_from(&name) {
+                Ok(StemmingConfig::Language(algorithm)) => Ok(StopwordsConfig::Language(algorithm)),
+                Ok(StemmingConfig::None) => Ok(StopwordsConfig::None),
+                Err(_) => Err(format!("Unexpected value `{name}`, expected `none`, a language supported by https://snowballstem.org/ (e.g. `Dutch`), or a list of words")),
+            },
+        }
+    }
+}
+
+impl From<StopwordsConfig> for StopwordsConfigValue {
+    fn from(stopwords_config: StopwordsConfig) -> Self {
+        match stopwords_config {
+            StopwordsConfig::None => StopwordsConfigValue::Name("none".to_string()),
+            StopwordsConfig::Language(algorithm) => {
+                StopwordsConfigValue::Name(String::from(StemmingConfig::Language(algorithm)))
+            }
+            StopwordsConfig::Custom(words) => StopwordsConfigValue::List(words),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    fn stopwords_from_toml(toml: &str) -> Result<StopwordsConfig, toml::de::Error> {
+        toml::from_str::<Config>(&format!("[input]\n{toml}"))
+            .map(|config| config.input.stopwords.unwrap())
+    }
+
+    #[test]
+    fn test_language() {
+        assert_eq!(
+            stopwords_from_toml("stopwords = \"French\"").unwrap(),
+            StopwordsConfig::Language(Algorithm::French)
+        );
+    }
+
+    #[test]
+    fn test_none() {
+        assert_eq!(
+            stopwords_from_toml("stopwords = \"none\"").unwrap(),
+            StopwordsConfig::None
+        );
+    }
+
+    #[test]
+    fn test_custom_list() {
+        assert_eq!(
+            stopwords_from_toml("stopwords = [\"stork\", \"search\"]").unwrap(),
+            StopwordsConfig::Custom(vec!["stork".to_string(), "search".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_json_custom_list() {
+        let config: Config =
+            serde_json::from_str(r#"{"input": {"stopwords": ["stork"]}}"#).unwrap();
+        assert_eq!(
+            config.input.stopwords,
+            Some(StopwordsConfig::Custom(vec!["stork".to_string()]))
+        );
+    }
+
+    #[test]
+    fn test_error() {
+        assert!(stopwords_from_toml("stopwords = \"Blorp\"").is_err());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for stopwords_config in [
+            StopwordsConfig::None,
+            StopwordsConfig::Language(Algorithm::Dutch),
+            StopwordsConfig::Custom(vec!["stork".to_string()]),
+        ] {
+            let json = serde_json::to_string(&stopwords_config).unwrap();
+            assert_eq!(
+                serde_json::from_str::<StopwordsConfig>(&json).unwrap(),
+                stopwords_config
+            );
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+
+/**
+ * The words a synonym maps to. In a config file, this is either a single word
+ * (`k8s = "kubernetes"`) or a list of words (`js = ["javascript", "ecmascript"]`).
+ */
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum Synonyms {
+    Word(String),
+    Words(Vec<String>),
+}
+
+impl Synonyms {
+    pub fn words(&self) -> Vec<&str> {
+        match self {
+            Synonyms::Word(word) => vec![word],
+            Synonyms::Words(words) => words.iter().map(String::as_str).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_inline_table() {
+        let config: Config = toml::from_str(
+            r#"
+[input.synonyms]
+k8s = "kubernetes"
+js = ["javascript", "ecmascript"]
+"#,
+        )
+        .unwrap();
+
+        let expected: BTreeMap<String, Synonyms> = [
+            (
+                "js".to_string(),
+                Synonyms::Words(vec!["javascript".to_string(), "ecmascript".to_string()]),
+            ),
+            ("k8s".to_string(), Synonyms::Word("kubernetes".to_string())),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(config.input.synonyms, expected);
+        assert_eq!(
+            config.input.synonyms["js"].words(),
+            vec!["javascript", "ecmascript"]
+        );
+    }
+}
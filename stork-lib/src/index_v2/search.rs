@@ -254,6 +254,8 @@ pub fn search(index: &Index, query: &str) -> Output {
         results: output_results,
         total_hit_count: *total_len,
         url_prefix: String::default(),
+        facets: None,
+        suggestions: vec![],
     }
 }
 
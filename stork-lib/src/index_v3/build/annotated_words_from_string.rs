@@ -1,32 +1,54 @@
-use crate::{index_v3::AnnotatedWord, InternalWordAnnotation};
+use crate::{
+    config::Tokenizer,
+    index_v3::{tokenizer::split_into_annotated_words, AnnotatedWord},
+    InternalWordAnnotation,
+};
 
 pub(super) trait AnnotatedWordable {
-    fn make_annotated_words(&self) -> Vec<AnnotatedWord>;
-    fn make_annotated_words_with_annotations<F>(&self, closure: F) -> Vec<AnnotatedWord>
+    fn make_annotated_words(&self, tokenizer: Tokenizer) -> Vec<AnnotatedWord>;
+    fn make_annotated_words_with_annotations<F>(
+        &self,
+        tokenizer: Tokenizer,
+        closure: F,
+    ) -> Vec<AnnotatedWord>
     where
         F: Fn(&str, &mut Vec<InternalWordAnnotation>);
 }
 
 impl AnnotatedWordable for str {
-    fn make_annotated_words(&self) -> Vec<AnnotatedWord> {
-        self.make_annotated_words_with_annotations(|_, _| {})
+    fn make_annotated_words(&self, tokenizer: Tokenizer) -> Vec<AnnotatedWord> {
+        self.make_annotated_words_with_annotations(tokenizer, |_, _| {})
     }
 
     fn make_annotated_words_with_annotations<F: Fn(&str, &mut Vec<InternalWordAnnotation>)>(
         &self,
+        tokenizer: Tokenizer,
         closure: F,
     ) -> Vec<AnnotatedWord> {
-        self.split(|c: char| c.is_ascii_whitespace() || c == '-')
-            .map(str::trim)
-            .filter(|s| !s.is_empty())
-            .map(|w| {
-                let mut internal_annotations: Vec<InternalWordAnnotation> = Vec::new();
-                closure(w, &mut internal_annotations);
-                AnnotatedWord {
+        let words = match tokenizer {
+            Tokenizer::Standard => self
+                .split(|c: char| c.is_ascii_whitespace() || c == '-')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(|w| AnnotatedWord {
                     word: w.to_string(),
-                    internal_annotations,
                     ..AnnotatedWord::default()
-                }
+                })
+                .collect(),
+
+            // Hyphenated words are indexed as separate words. Replacing
+            // hyphens keeps every other character where it was.
+            Tokenizer::CJKBigram => split_into_annotated_words(&self.replace('-', " "), tokenizer),
+        };
+
+        words
+            .into_iter()
+            .map(|mut annotated_word| {
+                closure(
+                    &annotated_word.word,
+                    &mut annotated_word.internal_annotations,
+                );
+                annotated_word
             })
             .collect()
     }
@@ -35,21 +57,25 @@ impl AnnotatedWordable for str {
 #[cfg(test)]
 mod tests {
 
-    use crate::InternalWordAnnotation;
+    use crate::{config::Tokenizer, InternalWordAnnotation};
 
     use super::AnnotatedWordable;
 
     #[test]
     fn annotated_words_split_on_hyphens() {
         let expected: usize = 3;
-        let computed = "Hastings-on-hudson".make_annotated_words().len();
+        let computed = "Hastings-on-hudson"
+            .make_annotated_words(Tokenizer::Standard)
+            .len();
         assert_eq!(expected, computed);
     }
 
     #[test]
     fn annotated_words_split_on_whitespace() {
         let expected: usize = 3;
-        let computed = "Hastings on hudson".make_annotated_words().len();
+        let computed = "Hastings on hudson"
+            .make_annotated_words(Tokenizer::Standard)
+            .len();
         assert_eq!(expected, computed);
     }
 
@@ -57,7 +83,7 @@ mod tests {
     fn annotated_words_split_on_multiple_whitespace() {
         let expected: usize = 3;
         let computed = "Hastings         on  \n \t hudson"
-            .make_annotated_words()
+            .make_annotated_words(Tokenizer::Standard)
             .len();
         assert_eq!(expected, computed);
     }
@@ -65,7 +91,10 @@ mod tests {
     #[test]
     fn annotated_words_can_correctly_annotate() {
         let computed = "Hastings         on  \n \t hudson".make_annotated_words_with_annotations(
-            |word, vec| vec.push(InternalWordAnnotation::UrlSuffix(word.to_string())),
+            Tokenizer::Standard,
+            |word, vec| {
+                vec.push(InternalWordAnnotation::UrlSuffix(word.to_string()));
+            },
         );
 
         assert_eq!(3, computed.len());
@@ -79,4 +108,14 @@ mod tests {
             computed[2].internal_annotations[0]
         );
     }
+
+    #[test]
+    fn standard_tokenizer_only_splits_on_ascii_whitespace_and_hyphens() {
+        let words: Vec<String> = "Hastings-on-hudson\u{a0}is 東京都"
+            .make_annotated_words(Tokenizer::Standard)
+            .into_iter()
+            .map(|annotated_word| annotated_word.word)
+            .collect();
+        assert_eq!(words, vec!["Hastings", "on", "hudson\u{a0}is", "東京都"]);
+    }
 }
@@ -1,4 +1,4 @@
-use std::{fmt, path::PathBuf};
+use std::{fmt, io, path::PathBuf};
 use thiserror::Error;
 
 use crate::config::File;
@@ -8,9 +8,15 @@ pub enum WordListGenerationError {
     #[error("SRT file could not be parsed.")]
     InvalidSRT,
 
+    #[error("WebVTT file could not be parsed.")]
+    InvalidWebVTT,
+
     #[error("The file `{0}` could not be found.")]
     FileNotFound(PathBuf),
 
+    #[error("The file `{0}` could not be read: {1}")]
+    FileNotRead(PathBuf, io::ErrorKind),
+
     #[error("Could not determine the file's filetype. Please give this file a file extension Stork knows about, or disambiguate the file's filetype within your config.")]
     CannotDetermineFiletype,
 
@@ -23,6 +29,9 @@ pub enum WordListGenerationError {
     #[error("When fetched, the web page returned a {0} status code.")]
     WebPageErrorfulStatusCode(u16),
 
+    #[error("The web page's contents could not be read: {0}")]
+    WebPageNotRead(io::ErrorKind),
+
     #[error("Content-Type is not present or invalid")]
     UnknownContentType,
 
@@ -31,6 +40,12 @@ pub enum WordListGenerationError {
 
     #[error("Stork was not built with the `web-scraping` feature enabled.")]
     FeatureNotAvailable,
+
+    #[error("The file has no `url`, `path`, `src_url`, or `contents` to read from.")]
+    NoDataSource,
+
+    #[error("Sitemaps, crawls, and JSON and CSV feeds list other files to index, and can't be read as a file themselves.")]
+    ListingDataSource,
 }
 
 fn pluralize_with_count(count: usize, singular: &str, plural: &str) -> String {
@@ -51,6 +66,39 @@ pub enum IndexGenerationError {
         DocumentError::display_list(.0)
     )]
     PartialDocumentErrors(Vec<DocumentError>),
+
+    #[error("The synonyms file `{0}` could not be found.")]
+    SynonymsFileNotFound(PathBuf),
+
+    #[error("The synonyms file `{0}` could not be read: {1}")]
+    SynonymsFileNotRead(PathBuf, io::ErrorKind),
+
+    #[error("Line {0} of the synonyms file should look like `word = synonym, synonym`.")]
+    InvalidSynonymsFileLine(usize),
+
+    #[error("The source directory `{0}` could not be found.")]
+    SourceDirectoryNotFound(PathBuf),
+
+    #[error("The source pattern `{0}` is not a valid glob.")]
+    InvalidSourcePattern(String),
+
+    #[error("The sitemap `{0}` could not be read: {1}")]
+    SitemapNotRead(String, WordListGenerationError),
+
+    #[error("The sitemap `{0}` is not a valid `urlset` or `sitemapindex` document.")]
+    InvalidSitemap(String),
+
+    #[error("The page `{0}` to start crawling from could not be found.")]
+    CrawlStartNotFound(PathBuf),
+
+    #[error("A page linked from the crawl starting at `{0}` could not be read: {1}")]
+    CrawledPageNotRead(String, WordListGenerationError),
+
+    #[error("The feed `{0}` could not be read: {1}")]
+    FeedNotRead(String, WordListGenerationError),
+
+    #[error("The feed `{0}` could not be parsed: {1}")]
+    InvalidFeed(String, String),
 }
 
 impl PartialEq for IndexGenerationError {
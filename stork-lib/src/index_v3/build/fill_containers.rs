@@ -2,9 +2,11 @@ use rust_stemmers::Stemmer;
 use std::{collections::BTreeMap, convert::TryInto, ops::Range};
 
 use crate::{
-    config::Config,
+    config::{Config, Ranking, Tokenizer},
     index_v3::{
+        normalization::normalize,
         scores::{PREFIX_SCORE, STEM_SCORE},
+        tokenizer::{char_is_cjk, char_is_cjk_ideograph, index_terms},
         AnnotatedWord, Container, Excerpt, SearchResult, WordListSource,
     },
 };
@@ -18,10 +20,11 @@ pub fn fill_containers(
     config: &Config,
     intermediate_entries: &[NormalizedEntry],
     stems: &BTreeMap<String, Vec<String>>,
+    stopwords: &[String],
     containers: &mut BTreeMap<String, Container>,
 ) {
     for (entry_index, entry) in intermediate_entries.iter().enumerate() {
-        let words_in_title: Vec<AnnotatedWord> = entry.title.make_annotated_words();
+        let words_in_title: Vec<AnnotatedWord> = entry.title.make_annotated_words(entry.tokenizer);
         let words_in_contents: Vec<AnnotatedWord> = entry.annotated_word_list.word_list.clone();
 
         let word_lists = vec![
@@ -30,14 +33,28 @@ pub fn fill_containers(
         ];
 
         for (source, word_list) in word_lists {
-            for (word_index, annotated_word) in word_list.iter().enumerate() {
-                let normalized_word =
-                    remove_surrounding_punctuation(&annotated_word.word.to_lowercase());
+            let normalized_words: Vec<String> = word_list
+                .iter()
+                .map(|annotated_word| {
+                    remove_surrounding_punctuation(&normalize(
+                        &annotated_word.word,
+                        config.input.normalization,
+                    ))
+                })
+                .collect();
+            let terms = index_terms(&normalized_words, entry.tokenizer);
 
+            for (word_index, (annotated_word, normalized_word)) in
+                word_list.iter().zip(terms).enumerate()
+            {
                 if normalized_word.is_empty() {
                     continue;
                 }
 
+                if config.input.remove_stopwords && stopwords.contains(&normalized_word) {
+                    continue;
+                }
+
                 fill_container_results_map(
                     config,
                     containers,
@@ -53,6 +70,7 @@ pub fn fill_containers(
                 fill_other_containers_alias_maps_with_prefixes(
                     config.input.minimum_indexed_substring_length,
                     config.input.minimum_index_ideographic_substring_length,
+                    entry.tokenizer,
                     containers,
                     &normalized_word,
                 );
@@ -88,6 +106,10 @@ fn fill_container_results_map(
         .entry(entry_index)
         .or_insert_with(SearchResult::new);
 
+    if config.output.ranking == Ranking::BM25 {
+        entry_result.term_frequency += 1;
+    }
+
     if config.output.excerpts_per_result > 0 {
         entry_result.excerpts.push(Excerpt {
             word_index,
@@ -101,12 +123,13 @@ fn fill_container_results_map(
 fn fill_other_containers_alias_maps_with_prefixes(
     prefix_length: u8,
     ideograph_prefix_length: u8,
+    tokenizer: Tokenizer,
     containers: &mut BTreeMap<String, Container>,
     normalized_word: &str,
 ) {
     let chars: Vec<char> = normalized_word.chars().collect();
 
-    let substring_max_length_range: Range<usize> = if string_is_cjk_ideographic(&chars) {
+    let substring_max_length_range: Range<usize> = if string_is_cjk(&chars, tokenizer) {
         (ideograph_prefix_length as usize)..chars.len()
     } else {
         (prefix_length as usize)..chars.len()
@@ -154,50 +177,23 @@ fn fill_other_containers_alias_maps_with_reverse_stems(
     }
 }
 
-fn string_is_cjk_ideographic(s: &[char]) -> bool {
-    s.iter()
-        .map(char_is_cjk_ideograph)
-        .fold(true, |acc, x| acc & x)
-}
-
-#[allow(clippy::trivially_copy_pass_by_ref)]
-fn char_is_cjk_ideograph(c: &char) -> bool {
-    // Block ranges sourced from https://en.wikipedia.org/wiki/CJK_Unified_Ideographs#CJK_Unified_Ideographs_blocks
-    matches!(c,
-        // CJK Unified Ideographs
-        '\u{4E00}'..='\u{62FF}' |
-        '\u{6300}'..='\u{77FF}' |
-        '\u{7800}'..='\u{8CFF}' |
-        '\u{8D00}'..='\u{9FFF}' |
-        // CJK Unified Ideographs Extension A
-        '\u{3400}'..='\u{4DBF}' |
-        // CJK Unified Ideographs Extension B
-        '\u{20000}'..='\u{215FF}' |
-        '\u{21600}'..='\u{230FF}' |
-        '\u{23100}'..='\u{245FF}' |
-        '\u{24600}'..='\u{260FF}' |
-        '\u{26100}'..='\u{275FF}' |
-        '\u{27600}'..='\u{290FF}' |
-        '\u{29100}'..='\u{2A6DF}' |
-        // CJK Unified Ideographs Extension C
-        '\u{2A700}'..='\u{2B73F}' |
-        // CJK Unified Ideographs Extension D
-        '\u{2B740}'..='\u{2B81F}' |
-        // CJK Unified Ideographs Extension E
-        '\u{2B820}'..='\u{2CEAF}' |
-        // CJK Unified Ideographs Extension F
-        '\u{2CEB0}'..='\u{2EBEF}' |
-        // CJK Unified Ideographs Extension G
-        '\u{30000}'..='\u{3134F}' |
-        // CJK Compatibility Ideographs
-        '\u{F900}'..='\u{FAFF}'
-    )
+/**
+ * Whether a word gets the shorter ideographic prefixes. Entries split into
+ * CJK bigrams count kana and Hangul too, since their words are never more
+ * than two characters long; other entries only count ideographs, as they
+ * always have.
+ */
+fn string_is_cjk(s: &[char], tokenizer: Tokenizer) -> bool {
+    match tokenizer {
+        Tokenizer::CJKBigram => s.iter().all(|c| char_is_cjk(*c)),
+        Tokenizer::Standard => s.iter().all(|c| char_is_cjk_ideograph(*c)),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{
-        config::Config,
+        config::{Config, Tokenizer},
         index_v3::{build::intermediate_entry::NormalizedEntry, AnnotatedWordList},
     };
     use std::collections::{BTreeMap, HashMap};
@@ -212,6 +208,8 @@ mod tests {
             url: String::new(),
             fields: HashMap::default(),
             stem_algorithm: None,
+            tokenizer: Tokenizer::Standard,
+            boost: None,
         };
 
         let mut containers = BTreeMap::default();
@@ -220,6 +218,7 @@ mod tests {
             &Config::default(),
             &[intermediate_entry],
             &BTreeMap::default(),
+            &[],
             &mut containers,
         );
 
@@ -229,4 +228,31 @@ mod tests {
             containers.keys()
         );
     }
+
+    #[test]
+    fn only_ideographs_get_ideographic_prefixes_with_the_standard_tokenizer() {
+        let intermediate_entry = NormalizedEntry {
+            annotated_word_list: AnnotatedWordList { word_list: vec![] },
+            title: "東京都 すみません".to_string(),
+            url: String::new(),
+            fields: HashMap::default(),
+            stem_algorithm: None,
+            tokenizer: Tokenizer::Standard,
+            boost: None,
+        };
+
+        let mut containers = BTreeMap::default();
+
+        fill_containers(
+            &Config::default(),
+            &[intermediate_entry],
+            &BTreeMap::default(),
+            &[],
+            &mut containers,
+        );
+
+        assert!(containers.contains_key("東"));
+        assert!(!containers.contains_key("す"));
+        assert!(containers.contains_key("すみま"));
+    }
 }
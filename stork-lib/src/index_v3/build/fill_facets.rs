@@ -0,0 +1,85 @@
+use std::collections::BTreeMap;
+
+use crate::{config::Config, index_v3::FacetValues};
+
+use super::intermediate_entry::NormalizedEntry;
+
+pub fn fill_facets(
+    config: &Config,
+    intermediate_entries: &[NormalizedEntry],
+    facets: &mut BTreeMap<String, FacetValues>,
+) {
+    for key in &config.output.facets {
+        let facet_values = facets.entry(key.clone()).or_default();
+
+        for entry in intermediate_entries {
+            let value_index = entry.fields.get(key).map(|value| {
+                facet_values
+                    .values
+                    .iter()
+                    .position(|existing| existing == value)
+                    .unwrap_or_else(|| {
+                        facet_values.values.push(value.clone());
+                        facet_values.values.len() - 1
+                    })
+            });
+
+            facet_values.entry_values.push(value_index);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, HashMap};
+
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        config::{Config, OutputConfig, Tokenizer},
+        index_v3::{build::intermediate_entry::NormalizedEntry, AnnotatedWordList},
+    };
+
+    use super::fill_facets;
+
+    fn entry_with_category(category: Option<&str>) -> NormalizedEntry {
+        NormalizedEntry {
+            annotated_word_list: AnnotatedWordList { word_list: vec![] },
+            stem_algorithm: None,
+            tokenizer: Tokenizer::Standard,
+            title: String::new(),
+            url: String::new(),
+            fields: category
+                .map(|c| HashMap::from([("category".to_string(), c.to_string())]))
+                .unwrap_or_default(),
+            boost: None,
+        }
+    }
+
+    #[test]
+    fn facet_values_are_stored_once() {
+        let config = Config {
+            output: OutputConfig {
+                facets: vec!["category".to_string()],
+                ..OutputConfig::default()
+            },
+            ..Config::default()
+        };
+
+        let mut facets = BTreeMap::new();
+        fill_facets(
+            &config,
+            &[
+                entry_with_category(Some("Guide")),
+                entry_with_category(None),
+                entry_with_category(Some("API")),
+                entry_with_category(Some("Guide")),
+            ],
+            &mut facets,
+        );
+
+        let category = &facets["category"];
+        assert_eq!(category.values, vec!["Guide", "API"]);
+        assert_eq!(category.entry_values, vec![Some(0), None, Some(1), Some(0)]);
+    }
+}
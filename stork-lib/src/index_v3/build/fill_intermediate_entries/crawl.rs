@@ -0,0 +1,408 @@
+use std::{
+    collections::{HashSet, VecDeque},
+    path::{Path, PathBuf},
+};
+
+use kuchiki::{traits::TendrilSink, NodeRef};
+
+use crate::config::{DataSource, File, Filetype};
+
+use super::data_source_readers::read_text;
+use super::IndexGenerationError;
+
+/**
+ * Expands a file whose source is a crawl into one file for each HTML page
+ * that can be reached from the starting page by following relative links.
+ * Links to other sites and to pages outside the crawled directory aren't
+ * followed.
+ *
+ * Each page gets a URL from its path inside the crawled directory, like
+ * `/docs/` for `docs/index.html`, and gets the crawl file's fields and
+ * overrides. Pages whose robots meta tag says `noindex`, or whose canonical
+ * link points to a different page, aren't indexed, though their links are
+ * still followed unless the robots meta tag also says `nofollow`.
+ */
+pub(super) fn expand_crawl(
+    file: &File,
+    start: &str,
+    base_directory: &str,
+) -> Result<Vec<File>, IndexGenerationError> {
+    let start_path = Path::new(base_directory).join(start);
+    let (root, start_page) = if start_path.is_dir() {
+        (PathBuf::from(start), "index.html".to_string())
+    } else {
+        let start = Path::new(start);
+        let root = find_site_root(start, base_directory);
+        let start_page = start
+            .strip_prefix(&root)
+            .unwrap_or(start)
+            .to_string_lossy()
+            .replace('\\', "/");
+        (root, start_page)
+    };
+
+    let root_directory = Path::new(base_directory).join(&root);
+    if !root_directory.join(&start_page).is_file() {
+        return Err(IndexGenerationError::CrawlStartNotFound(
+            root_directory.join(start_page),
+        ));
+    }
+
+    let mut queue = VecDeque::from([start_page.clone()]);
+    let mut visited_pages = HashSet::from([start_page]);
+    let mut files = vec![];
+
+    let mut enqueue = |page: String, queue: &mut VecDeque<String>| {
+        if visited_pages.insert(page.clone()) {
+            queue.push_back(page);
+        }
+    };
+
+    while let Some(page) = queue.pop_front() {
+        let contents = read_text(&root.join(&page).to_string_lossy(), base_directory)
+            .map_err(|error| IndexGenerationError::CrawledPageNotRead(start.to_string(), error))?;
+        let document = kuchiki::parse_html().one(contents);
+        let robots = RobotsDirectives::from_document(&document);
+
+        if !robots.nofollow {
+            for href in attribute_values(&document, "a[href]", "href") {
+                if let Some(linked_page) =
+                    resolve_link(&page, &href).and_then(|link| find_page(&root_directory, &link))
+                {
+                    enqueue(linked_page, &mut queue);
+                }
+            }
+        }
+
+        let canonical_page = canonical_href(&document).map(|href| {
+            resolve_link(&page, strip_origin(&href))
+                .and_then(|link| find_page(&root_directory, &link))
+        });
+
+        match canonical_page {
+            Some(Some(canonical_page)) if canonical_page != page => {
+                enqueue(canonical_page, &mut queue);
+                continue;
+            }
+            Some(None) => continue,
+            _ if robots.noindex => continue,
+            _ => {}
+        }
+
+        files.push(File {
+            title: String::new(),
+            url: page_url(&page),
+            explicit_source: Some(DataSource::FilePath(
+                root.join(&page).to_string_lossy().replace('\\', "/"),
+            )),
+            id: None,
+            filetype: Some(Filetype::HTML),
+            ..file.clone()
+        });
+    }
+
+    Ok(files)
+}
+
+/**
+ * The directory a crawl that starts from a page treats as the site's root:
+ * the outermost directory above the page that has an `index.html`, so that
+ * the page keeps its URL and links out of its own directory are followed.
+ * If none of them do, it's the page's own directory.
+ */
+fn find_site_root(start: &Path, base_directory: &str) -> PathBuf {
+    let page_directory = start.parent().unwrap_or_else(|| Path::new(""));
+    page_directory
+        .ancestors()
+        .filter(|directory| {
+            Path::new(base_directory)
+                .join(directory)
+                .join("index.html")
+                .is_file()
+        })
+        .last()
+        .unwrap_or(page_directory)
+        .to_path_buf()
+}
+
+#[derive(Default)]
+struct RobotsDirectives {
+    noindex: bool,
+    nofollow: bool,
+}
+
+impl RobotsDirectives {
+    fn from_document(document: &NodeRef) -> Self {
+        let mut directives = RobotsDirectives::default();
+
+        let robots_contents = document
+            .select("meta[name][content]")
+            .into_iter()
+            .flatten()
+            .filter_map(|meta| {
+                let attributes = meta.attributes.borrow();
+                attributes
+                    .get("name")
+                    .filter(|name| name.eq_ignore_ascii_case("robots"))
+                    .and(attributes.get("content").map(str::to_lowercase))
+            })
+            .collect::<Vec<String>>();
+
+        for directive in robots_contents
+            .iter()
+            .flat_map(|content| content.split(','))
+        {
+            match directive.trim() {
+                "noindex" => directives.noindex = true,
+                "nofollow" => directives.nofollow = true,
+                "none" => {
+                    directives.noindex = true;
+                    directives.nofollow = true;
+                }
+                _ => {}
+            }
+        }
+
+        directives
+    }
+}
+
+fn attribute_values(document: &NodeRef, selector: &str, attribute: &str) -> Vec<String> {
+    document
+        .select(selector)
+        .into_iter()
+        .flatten()
+        .filter_map(|element| {
+            element
+                .attributes
+                .borrow()
+                .get(attribute)
+                .map(ToString::to_string)
+        })
+        .collect()
+}
+
+fn canonical_href(document: &NodeRef) -> Option<String> {
+    document
+        .select("link[rel][href]")
+        .ok()?
+        .find(|link| {
+            link.attributes.borrow().get("rel").is_some_and(|rel| {
+                rel.split_whitespace()
+                    .any(|rel| rel.eq_ignore_ascii_case("canonical"))
+            })
+        })
+        .and_then(|link| {
+            link.attributes
+                .borrow()
+                .get("href")
+                .map(ToString::to_string)
+        })
+}
+
+/**
+ * Turns an absolute URL like `https://example.com/docs/` into a path like
+ * `/docs/`, so that canonical links to the deployed site can be matched with
+ * pages in the crawled directory.
+ */
+fn strip_origin(href: &str) -> &str {
+    match href.split_once("://") {
+        Some((_, rest)) => rest.find('/').map_or("/", |index| &rest[index..]),
+        None => href,
+    }
+}
+
+/**
+ * Resolves a link on a page to a path relative to the crawled directory. A
+ * returned path that's empty or ends with `/` is a directory. Returns `None` for links to
+ * other sites, links that only change the fragment, and links that leave the
+ * crawled directory.
+ */
+fn resolve_link(page: &str, href: &str) -> Option<String> {
+    let href = href.trim();
+    let href = href.split(['#', '?']).next().unwrap_or_default();
+    let has_scheme = match (href.find(':'), href.find('/')) {
+        (Some(colon), Some(slash)) => colon < slash,
+        (Some(_), None) => true,
+        (None, _) => false,
+    };
+    if href.is_empty() || href.starts_with("//") || has_scheme {
+        return None;
+    }
+
+    let mut segments: Vec<&str> = if href.starts_with('/') {
+        vec![]
+    } else {
+        let mut segments: Vec<&str> = page.split('/').collect();
+        segments.pop();
+        segments
+    };
+
+    for segment in href.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                segments.pop()?;
+            }
+            segment => segments.push(segment),
+        }
+    }
+
+    let mut path = segments.join("/");
+    let is_directory = href.ends_with('/') || href.ends_with('.');
+    if is_directory && !path.is_empty() {
+        path.push('/');
+    }
+    Some(path)
+}
+
+/**
+ * Finds the HTML file that a resolved link points to, the way a static file
+ * server would: `docs/` and `docs` can both mean `docs/index.html`, and
+ * `about` can mean `about.html`.
+ */
+fn find_page(root_directory: &Path, link: &str) -> Option<String> {
+    let candidates = if link.is_empty() || link.ends_with('/') {
+        vec![format!("{link}index.html")]
+    } else {
+        vec![
+            link.to_string(),
+            format!("{link}/index.html"),
+            format!("{link}.html"),
+        ]
+    };
+
+    candidates.into_iter().find(|candidate| {
+        let is_html = Path::new(candidate)
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .is_some_and(|extension| {
+                extension.eq_ignore_ascii_case("html") || extension.eq_ignore_ascii_case("htm")
+            });
+        is_html && root_directory.join(candidate).is_file()
+    })
+}
+
+/**
+ * The URL a static file server would serve a page at, relative to the site's
+ * root: `docs/index.html` is served at `/docs/`.
+ */
+fn page_url(page: &str) -> String {
+    let url = page.strip_suffix("index.html").unwrap_or(page);
+    format!("/{url}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index_v3::build::errors::WordListGenerationError;
+    use pretty_assertions::assert_eq;
+    use std::collections::HashMap;
+
+    #[test]
+    fn links_resolve_relative_to_the_page() {
+        assert_eq!(
+            resolve_link("docs/index.html", "install.html"),
+            Some("docs/install.html".to_string())
+        );
+        assert_eq!(
+            resolve_link("docs/install.html", "../about/#team"),
+            Some("about/".to_string())
+        );
+        assert_eq!(
+            resolve_link("docs/install.html", "/blog?page=2"),
+            Some("blog".to_string())
+        );
+        assert_eq!(resolve_link("docs/index.html", "/"), Some(String::new()));
+        assert_eq!(resolve_link("index.html", "../outside.html"), None);
+        assert_eq!(resolve_link("index.html", "#top"), None);
+        assert_eq!(resolve_link("index.html", "https://example.com/"), None);
+        assert_eq!(resolve_link("index.html", "//example.com/"), None);
+        assert_eq!(resolve_link("index.html", "mailto:hi@example.com"), None);
+    }
+
+    #[test]
+    fn pages_are_served_at_their_directory() {
+        assert_eq!(page_url("index.html"), "/");
+        assert_eq!(page_url("docs/index.html"), "/docs/");
+        assert_eq!(page_url("docs/install.html"), "/docs/install.html");
+        assert_eq!(strip_origin("https://example.com/docs/"), "/docs/");
+        assert_eq!(strip_origin("https://example.com"), "/");
+    }
+
+    #[test]
+    fn crawl_follows_links_and_honors_robots_and_canonical() {
+        let file = File {
+            explicit_source: Some(DataSource::Crawl("crawl/public".to_string())),
+            fields: HashMap::from([("site".to_string(), "docs".to_string())]),
+            ..File::default()
+        };
+
+        let files = expand_crawl(&file, "crawl/public", "../test-assets").unwrap();
+
+        assert_eq!(
+            files
+                .iter()
+                .map(|file| (file.url.as_str(), file.source()))
+                .collect::<Vec<(&str, DataSource)>>(),
+            vec![
+                (
+                    "/",
+                    DataSource::FilePath("crawl/public/index.html".to_string())
+                ),
+                (
+                    "/docs/",
+                    DataSource::FilePath("crawl/public/docs/index.html".to_string())
+                ),
+                (
+                    "/about.html",
+                    DataSource::FilePath("crawl/public/about.html".to_string())
+                ),
+                (
+                    "/docs/install.html",
+                    DataSource::FilePath("crawl/public/docs/install.html".to_string())
+                ),
+                (
+                    "/docs/secret.html",
+                    DataSource::FilePath("crawl/public/docs/secret.html".to_string())
+                ),
+            ]
+        );
+        assert!(files
+            .iter()
+            .all(|file| file.fields.get("site") == Some(&"docs".to_string())));
+    }
+
+    #[test]
+    fn crawl_can_start_from_a_page() {
+        let file = File::default();
+        let files =
+            expand_crawl(&file, "crawl/public/docs/install.html", "../test-assets").unwrap();
+        assert_eq!(files[0].url, "/docs/install.html");
+        assert_eq!(
+            files[0].source(),
+            DataSource::FilePath("crawl/public/docs/install.html".to_string())
+        );
+        assert!(files.iter().any(|file| file.url == "/about.html"));
+
+        assert!(matches!(
+            expand_crawl(&file, "crawl/missing", "../test-assets").unwrap_err(),
+            IndexGenerationError::CrawlStartNotFound(path)
+                if path == Path::new("../test-assets/crawl/missing")
+        ));
+    }
+
+    #[test]
+    fn crawl_fails_on_a_linked_page_that_cannot_be_read() {
+        let error =
+            expand_crawl(&File::default(), "crawl/unreadable", "../test-assets").unwrap_err();
+        assert!(matches!(
+            error,
+            IndexGenerationError::CrawledPageNotRead(
+                start,
+                WordListGenerationError::FileNotRead(path, std::io::ErrorKind::InvalidData)
+            ) if start == "crawl/unreadable"
+                && path == Path::new("../test-assets/crawl/unreadable/binary.html")
+        ));
+    }
+}
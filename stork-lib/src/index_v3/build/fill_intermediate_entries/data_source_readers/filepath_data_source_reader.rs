@@ -26,14 +26,18 @@ pub(crate) fn read(
         buffer,
         filetype: config.file.filetype.clone().or(filetype_from_extension),
         frontmatter_fields: None,
+        frontmatter_title: None,
     })
 }
 
-fn get_filetype_from_path(path: &Path) -> Option<Filetype> {
+pub(crate) fn get_filetype_from_path(path: &Path) -> Option<Filetype> {
     let ext_str = path.extension()?.to_str()?;
     match String::from(ext_str).to_ascii_lowercase().as_ref() {
         "html" | "htm" => Some(Filetype::HTML),
         "srt" => Some(Filetype::SRTSubtitle),
+        "vtt" => Some(Filetype::WebVTT),
+        "adoc" | "asciidoc" => Some(Filetype::AsciiDoc),
+        "rst" => Some(Filetype::ReStructuredText),
         "txt" => Some(Filetype::PlainText),
         "markdown" | "mdown" | "md" => Some(Filetype::Markdown),
         _ => None,
@@ -17,17 +17,45 @@ pub fn read_from_data_source(
                 .clone()
                 .or(Some(Filetype::PlainText)),
             frontmatter_fields: None,
+            frontmatter_title: None,
         }),
 
         DataSource::URL(url) => return url_data_source_reader::read(url, reader_config),
         DataSource::FilePath(path) => filepath_data_source_reader::read(path, reader_config),
+        DataSource::Sitemap(_)
+        | DataSource::Crawl(_)
+        | DataSource::JSON(_)
+        | DataSource::CSV(_) => {
+            // These are expanded into the files they list before anything is
+            // read, so they only get here if a file was built by hand.
+            Err(WordListGenerationError::ListingDataSource)
+        }
     }
     .map(|read_result| read_result.extract_frontmatter(reader_config))
 }
 
+/**
+ * Reads a whole file as text, from a URL or from a path relative to the base
+ * directory. Used for files that list other files, like sitemaps and feeds.
+ */
+pub(crate) fn read_text(
+    location: &str,
+    base_directory: &str,
+) -> Result<String, WordListGenerationError> {
+    if location.starts_with("http://") || location.starts_with("https://") {
+        url_data_source_reader::fetch_text(location)
+    } else {
+        let path = std::path::Path::new(base_directory).join(location);
+        std::fs::read_to_string(&path).map_err(|error| match error.kind() {
+            std::io::ErrorKind::NotFound => WordListGenerationError::FileNotFound(path),
+            kind => WordListGenerationError::FileNotRead(path, kind),
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use std::collections::HashMap;
+    use std::{collections::HashMap, path::Path};
 
     use pretty_assertions::assert_eq;
 
@@ -36,7 +64,7 @@ mod tests {
         index_v3::build::fill_intermediate_entries::{ReadResult, ReaderConfig},
     };
 
-    use super::read_from_data_source;
+    use super::{read_from_data_source, read_text, WordListGenerationError};
 
     #[test]
     fn read_from_data_source_extracts_frontmatter() {
@@ -69,8 +97,42 @@ this _is_ the text"#
             ReadResult {
                 buffer: "# Header\n\nthis _is_ the text".to_string(),
                 filetype: Some(Filetype::Markdown),
-                frontmatter_fields: Some(HashMap::from([("key".to_string(), "value".to_string())]))
+                frontmatter_fields: Some(HashMap::from([("key".to_string(), "value".to_string())])),
+                frontmatter_title: None,
             }
         );
     }
+
+    #[test]
+    fn read_text_keeps_the_kind_of_error() {
+        let base_directory = "../test-assets";
+
+        assert!(matches!(
+            read_text("missing.xml", base_directory),
+            Err(WordListGenerationError::FileNotFound(path))
+                if path == Path::new(base_directory).join("missing.xml")
+        ));
+
+        // A directory exists, but can't be read as text
+        assert!(matches!(
+            read_text(".", base_directory),
+            Err(WordListGenerationError::FileNotRead(path, _))
+                if path == Path::new(base_directory).join(".")
+        ));
+    }
+
+    #[test]
+    fn sources_that_list_files_cannot_be_read_as_a_file() {
+        let error = read_from_data_source(&ReaderConfig {
+            global: InputConfig::default(),
+            file: File {
+                explicit_source: Some(DataSource::Sitemap("sitemap.xml".to_string())),
+                ..File::default()
+            },
+            output: OutputConfig::default(),
+        })
+        .unwrap_err();
+
+        assert_eq!(error, WordListGenerationError::ListingDataSource);
+    }
 }
@@ -8,6 +8,42 @@ pub(crate) fn read(
     Err(WordListGenerationError::FeatureNotAvailable)
 }
 
+#[cfg(not(feature = "build-v3-web-scraping"))]
+pub(crate) fn fetch_text(_url: &str) -> Result<String, WordListGenerationError> {
+    Err(WordListGenerationError::FeatureNotAvailable)
+}
+
+#[cfg(feature = "build-v3-web-scraping")]
+fn get(url: &str) -> Result<reqwest::blocking::Response, WordListGenerationError> {
+    let resp =
+        reqwest::blocking::get(url).map_err(|_| WordListGenerationError::WebPageNotFetched)?;
+
+    let _status =
+        resp.error_for_status_ref()
+            .map_err(|error| match error.status().map(|s| s.as_u16()) {
+                Some(status_code) => {
+                    WordListGenerationError::WebPageErrorfulStatusCode(status_code)
+                }
+                None => WordListGenerationError::WebPageNotFetched,
+            })?;
+
+    Ok(resp)
+}
+
+/**
+ * Fetches the body of a URL as text, whatever its content type.
+ */
+#[cfg(feature = "build-v3-web-scraping")]
+pub(crate) fn fetch_text(url: &str) -> Result<String, WordListGenerationError> {
+    use std::io::Read;
+
+    let mut buffer = String::new();
+    let _bytes_read = get(url)?
+        .read_to_string(&mut buffer)
+        .map_err(|error| WordListGenerationError::WebPageNotRead(error.kind()))?;
+    Ok(buffer)
+}
+
 #[cfg(feature = "build-v3-web-scraping")]
 pub(crate) fn read(
     url: &str,
@@ -21,21 +57,12 @@ pub(crate) fn read(
         match (mime.type_(), mime.subtype()) {
             (mime::TEXT, mime::PLAIN) => Some(Filetype::PlainText),
             (mime::TEXT, mime::HTML) => Some(Filetype::HTML),
+            (mime::TEXT, subtype) if subtype == "vtt" => Some(Filetype::WebVTT),
             _ => None,
         }
     }
 
-    let mut resp =
-        reqwest::blocking::get(url).map_err(|_| WordListGenerationError::WebPageNotFetched)?;
-
-    let _status =
-        resp.error_for_status_ref()
-            .map_err(|error| match error.status().map(|s| s.as_u16()) {
-                Some(status_code) => {
-                    WordListGenerationError::WebPageErrorfulStatusCode(status_code)
-                }
-                None => WordListGenerationError::WebPageNotFetched,
-            })?;
+    let mut resp = get(url)?;
 
     let mime_type: Mime = resp
         .headers()
@@ -57,5 +84,6 @@ pub(crate) fn read(
             .clone()
             .or_else(|| filetype_from_mime(&mime_type)),
         frontmatter_fields: None,
+        frontmatter_title: None,
     })
 }
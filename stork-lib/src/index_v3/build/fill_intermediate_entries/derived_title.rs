@@ -0,0 +1,124 @@
+use kuchiki::traits::TendrilSink;
+use pulldown_cmark::{Event, Parser, Tag};
+
+use crate::config::Filetype;
+
+use super::ReadResult;
+
+/**
+ * Finds a title for a file whose config doesn't set one. In order, this
+ * looks for:
+ *
+ * - the `title` in the document's frontmatter
+ * - for HTML documents, the `<title>` element, then the first `<h1>`
+ * - for Markdown documents, the first heading
+ */
+pub(super) fn derive_title(read_result: &ReadResult) -> Option<String> {
+    read_result
+        .frontmatter_title
+        .as_deref()
+        .and_then(clean_title)
+        .or_else(|| match read_result.filetype {
+            Some(Filetype::HTML) => html_title(&read_result.buffer),
+            Some(Filetype::Markdown) => markdown_title(&read_result.buffer),
+            _ => None,
+        })
+}
+
+fn html_title(buffer: &str) -> Option<String> {
+    let document = kuchiki::parse_html().one(buffer);
+
+    ["title", "h1"].iter().find_map(|selector| {
+        document
+            .select_first(selector)
+            .ok()
+            .and_then(|element| clean_title(&element.text_contents()))
+    })
+}
+
+fn markdown_title(buffer: &str) -> Option<String> {
+    let mut title = String::new();
+    let mut is_in_heading = false;
+
+    for event in Parser::new(buffer) {
+        match event {
+            Event::Start(Tag::Heading(..)) => is_in_heading = true,
+            Event::End(Tag::Heading(..)) => return clean_title(&title),
+            Event::Text(text) | Event::Code(text) if is_in_heading => title.push_str(&text),
+            _ => {}
+        }
+    }
+
+    None
+}
+
+fn clean_title(title: &str) -> Option<String> {
+    let title = title.split_whitespace().collect::<Vec<&str>>().join(" ");
+    if title.is_empty() {
+        None
+    } else {
+        Some(title)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn read_result(
+        buffer: &str,
+        filetype: Filetype,
+        frontmatter_title: Option<&str>,
+    ) -> ReadResult {
+        ReadResult {
+            buffer: buffer.to_string(),
+            filetype: Some(filetype),
+            frontmatter_fields: None,
+            frontmatter_title: frontmatter_title.map(ToString::to_string),
+        }
+    }
+
+    #[test]
+    fn frontmatter_title_comes_first() {
+        let result = read_result("# Heading", Filetype::Markdown, Some("From frontmatter"));
+        assert_eq!(derive_title(&result), Some("From frontmatter".to_string()));
+    }
+
+    #[test]
+    fn html_title_element_comes_before_h1() {
+        let html = "<html><head><title>\n  The   page\n</title></head><body><h1>Heading</h1></body></html>";
+        assert_eq!(
+            derive_title(&read_result(html, Filetype::HTML, None)),
+            Some("The page".to_string())
+        );
+
+        let html =
+            "<html><head><title> </title></head><body><h1>The <em>heading</em></h1></body></html>";
+        assert_eq!(
+            derive_title(&read_result(html, Filetype::HTML, None)),
+            Some("The heading".to_string())
+        );
+    }
+
+    #[test]
+    fn markdown_uses_the_first_heading() {
+        let markdown = "Intro text\n\n## Getting `stork` running\n\n# Later heading";
+        assert_eq!(
+            derive_title(&read_result(markdown, Filetype::Markdown, None)),
+            Some("Getting stork running".to_string())
+        );
+    }
+
+    #[test]
+    fn documents_without_titles_have_none() {
+        assert_eq!(
+            derive_title(&read_result("Just text", Filetype::Markdown, None)),
+            None
+        );
+        assert_eq!(
+            derive_title(&read_result("# Not a heading", Filetype::PlainText, None)),
+            None
+        );
+    }
+}
@@ -0,0 +1,88 @@
+use std::path::Path;
+
+/**
+ * Builds a URL for a file whose config doesn't set one, by filling in the
+ * URL template with parts of the file's path (relative to the base
+ * directory). For `posts/hello.md`:
+ *
+ * - `{path}` is `posts/hello.md`
+ * - `{dir}` is `posts`
+ * - `{stem}` is `hello`
+ * - `{ext}` is `md`
+ *
+ * So the template `/{dir}/{stem}/` gives `/posts/hello/`. Repeated slashes
+ * left by empty parts are collapsed.
+ */
+pub(super) fn derive_url(template: &str, path: &str) -> String {
+    let path = path.replace('\\', "/");
+    let path = path.trim_start_matches("./");
+    let path_ref = Path::new(path);
+
+    let dir = path_ref.parent().and_then(Path::to_str).unwrap_or_default();
+    let stem = path_ref
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or_default();
+    let ext = path_ref
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or_default();
+
+    let url = template
+        .replace("{path}", path)
+        .replace("{dir}", dir)
+        .replace("{stem}", stem)
+        .replace("{ext}", ext);
+
+    collapse_slashes(&url)
+}
+
+/**
+ * Collapses runs of slashes into one, except for the two after a URL's
+ * scheme, like `https://`.
+ */
+fn collapse_slashes(url: &str) -> String {
+    let mut output = String::with_capacity(url.len());
+    let mut previous: Option<char> = None;
+    let mut before_previous: Option<char> = None;
+
+    for c in url.chars() {
+        let is_repeated_slash = c == '/' && previous == Some('/');
+        let follows_scheme = before_previous == Some(':');
+        if !is_repeated_slash || follows_scheme {
+            output.push(c);
+        }
+        before_previous = previous;
+        previous = Some(c);
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn template_parts_come_from_the_path() {
+        assert_eq!(derive_url("{path}", "posts/hello.md"), "posts/hello.md");
+        assert_eq!(
+            derive_url("/{dir}/{stem}/", "posts/hello.md"),
+            "/posts/hello/"
+        );
+        assert_eq!(
+            derive_url("https://example.com/{dir}/{stem}.{ext}", "./a/b/c.html"),
+            "https://example.com/a/b/c.html"
+        );
+    }
+
+    #[test]
+    fn empty_parts_dont_leave_repeated_slashes() {
+        assert_eq!(derive_url("/{dir}/{stem}/", "hello.md"), "/hello/");
+        assert_eq!(
+            derive_url("https://example.com/{dir}/{stem}/", "hello.md"),
+            "https://example.com/hello/"
+        );
+    }
+}
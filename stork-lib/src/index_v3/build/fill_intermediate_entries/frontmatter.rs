@@ -17,18 +17,9 @@ pub fn parse_frontmatter(handling: &FrontmatterConfig, buffer: &str) -> (Fields,
             }
         }
         FrontmatterConfig::Parse => {
-            if let Ok((Some(Yaml::Hash(map)), text)) = parse_and_find_content(buffer) {
-                let fields = map
-                    .into_iter()
-                    .map(|(k, v)| {
-                        (
-                            k.into_string().unwrap_or_default(),
-                            v.clone().into_string().unwrap_or_else(|| {
-                                v.into_i64().map_or("error".to_string(), |i| i.to_string())
-                            }),
-                        )
-                    })
-                    .collect();
+            if let Ok((Some(yaml @ Yaml::Hash(_)), text)) = parse_and_find_content(buffer) {
+                let mut fields = HashMap::new();
+                flatten_yaml(None, yaml, &mut fields);
                 return (fields, text.trim().to_string());
             }
 
@@ -37,6 +28,53 @@ pub fn parse_frontmatter(handling: &FrontmatterConfig, buffer: &str) -> (Fields,
     }
 }
 
+/**
+ * Adds a YAML value to the fields map. Values inside hashes and arrays are
+ * added with dotted keys, so `author: { name: Jane }` becomes `author.name`
+ * and the first item of `tags: [a, b]` becomes `tags.0`. Null values are
+ * left out.
+ */
+fn flatten_yaml(key: Option<String>, yaml: Yaml, fields: &mut Fields) {
+    let child_key = |child: String| match &key {
+        Some(key) => format!("{key}.{child}"),
+        None => child,
+    };
+
+    match yaml {
+        Yaml::Hash(map) => {
+            for (child, value) in map {
+                if let Some(child) = yaml_key_to_string(child) {
+                    flatten_yaml(Some(child_key(child)), value, fields);
+                }
+            }
+        }
+        Yaml::Array(values) => {
+            for (index, value) in values.into_iter().enumerate() {
+                flatten_yaml(Some(child_key(index.to_string())), value, fields);
+            }
+        }
+        Yaml::String(value) | Yaml::Real(value) => {
+            fields.insert(key.unwrap_or_default(), value);
+        }
+        Yaml::Integer(value) => {
+            fields.insert(key.unwrap_or_default(), value.to_string());
+        }
+        Yaml::Boolean(value) => {
+            fields.insert(key.unwrap_or_default(), value.to_string());
+        }
+        Yaml::Null | Yaml::Alias(_) | Yaml::BadValue => {}
+    }
+}
+
+fn yaml_key_to_string(key: Yaml) -> Option<String> {
+    match key {
+        Yaml::String(key) | Yaml::Real(key) => Some(key),
+        Yaml::Integer(key) => Some(key.to_string()),
+        Yaml::Boolean(key) => Some(key.to_string()),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 #[allow(clippy::unnecessary_mut_passed)]
 mod tests {
@@ -71,7 +109,7 @@ this is not
                 ("this".to_string(), "is frontmatter".to_string()),
                 ("that takes".to_string(), "multiple lines".to_string()),
                 ("and has".to_string(), "22".to_string()),
-                ("different formats".to_string(), "error".to_string()),
+                ("different formats".to_string(), "+INF".to_string()),
             ]
             .iter()
             .cloned()
@@ -95,4 +133,41 @@ this is not
         let computed = (output.0, output.1);
         assert_eq!(expected, computed);
     }
+
+    #[test]
+    fn parse_option_flattens_nested_values() {
+        let expected: Fields = [
+            ("title".to_string(), "Nested".to_string()),
+            ("author.name".to_string(), "Jane".to_string()),
+            ("author.links.site".to_string(), "example.com".to_string()),
+            ("tags.0".to_string(), "rust".to_string()),
+            ("tags.1".to_string(), "search".to_string()),
+            ("draft".to_string(), "false".to_string()),
+            ("weight".to_string(), "1.5".to_string()),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+
+        let (fields, text) = parse_frontmatter(
+            &FrontmatterConfig::Parse,
+            r"---
+title: Nested
+author:
+  name: Jane
+  links:
+    site: example.com
+tags: [rust, search]
+draft: false
+weight: 1.5
+unset: ~
+---
+
+this is not
+        ",
+        );
+
+        assert_eq!(fields, expected);
+        assert_eq!(text, "this is not");
+    }
 }
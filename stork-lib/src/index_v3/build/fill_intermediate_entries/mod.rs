@@ -9,9 +9,25 @@ use word_list_generators::create_word_list;
 mod frontmatter;
 use self::frontmatter::parse_frontmatter;
 
+mod derived_title;
+use derived_title::derive_title;
+
+mod derived_url;
+use derived_url::derive_url;
+
+mod crawl;
+
+mod records;
+
+mod sitemap;
+
+mod sources;
+use sources::expand_sources;
+
 use super::{IndexGenerationError, NormalizedEntry};
 use crate::config::{
-    Config, DataSource, File, Filetype, InputConfig, OutputConfig, StemmingConfig,
+    Config, DataSource, File, Filetype, FrontmatterConfig, InputConfig, OutputConfig,
+    StemmingConfig, Tokenizer,
 };
 use indicatif::{ProgressBar, ProgressDrawTarget, ProgressIterator, ProgressStyle};
 use std::{collections::HashMap, convert::TryInto};
@@ -30,11 +46,30 @@ pub struct ReadResult {
     /// use the filetype here if it's available.
     pub(super) filetype: Option<Filetype>,
 
-    #[allow(dead_code)]
+    /// Fields parsed from the document's frontmatter, if the config asks for
+    /// frontmatter to be parsed.
     pub(super) frontmatter_fields: Option<HashMap<String, String>>,
+
+    /// The `title` from the document's frontmatter, if the config doesn't
+    /// set the file's title and the frontmatter isn't being ignored.
+    pub(super) frontmatter_title: Option<String>,
 }
 
 impl ReadResult {
+    /**
+     * Merges the fields parsed from the frontmatter with the fields declared
+     * in the config. When both have the same key, the config's value wins.
+     */
+    fn merge_fields(&self, file_fields: &HashMap<String, String>) -> HashMap<String, String> {
+        let mut fields = self.frontmatter_fields.clone().unwrap_or_default();
+        fields.extend(
+            file_fields
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone())),
+        );
+        fields
+    }
+
     fn extract_frontmatter(&self, config: &ReaderConfig) -> Self {
         let handling = config
             .file
@@ -44,10 +79,20 @@ impl ReadResult {
 
         let (frontmatter_fields, buffer) = parse_frontmatter(handling, &self.buffer);
 
+        let frontmatter_title = match handling {
+            _ if !config.file.title.is_empty() => None,
+            FrontmatterConfig::Ignore => None,
+            FrontmatterConfig::Omit => parse_frontmatter(&FrontmatterConfig::Parse, &self.buffer)
+                .0
+                .remove("title"),
+            FrontmatterConfig::Parse => frontmatter_fields.get("title").cloned(),
+        };
+
         ReadResult {
             buffer,
             filetype: self.filetype.clone(),
             frontmatter_fields: Some(frontmatter_fields),
+            frontmatter_title,
         }
     }
 }
@@ -71,6 +116,12 @@ impl ReaderConfig {
             StemmingConfig::None => None,
         }
     }
+
+    fn get_tokenizer(&self) -> Tokenizer {
+        self.file
+            .tokenizer_override
+            .unwrap_or(self.global.tokenizer)
+    }
 }
 
 pub(super) fn fill_intermediate_entries(
@@ -78,18 +129,15 @@ pub(super) fn fill_intermediate_entries(
     intermediate_entries: &mut Vec<NormalizedEntry>,
     document_errors: &mut Vec<DocumentError>,
 ) -> Result<(), IndexGenerationError> {
-    if config.input.files.is_empty() {
+    let files = expand_sources(&config.input)?;
+
+    if files.is_empty() {
         return Err(IndexGenerationError::NoFilesSpecified);
     }
 
-    let progress_bar = build_progress_bar(config);
+    let progress_bar = build_progress_bar(&files);
 
-    for stork_file in config
-        .input
-        .files
-        .iter()
-        .progress_with(progress_bar.clone())
-    {
+    for stork_file in files.iter().progress_with(progress_bar.clone()) {
         let reader_config = ReaderConfig {
             global: config.input.clone(),
             file: stork_file.clone(),
@@ -100,6 +148,10 @@ pub(super) fn fill_intermediate_entries(
 
         let intermediate_entry_result: Result<NormalizedEntry, WordListGenerationError> =
             || -> Result<NormalizedEntry, WordListGenerationError> {
+                if stork_file.explicit_source.is_none() && stork_file.url.is_empty() {
+                    return Err(WordListGenerationError::NoDataSource);
+                }
+
                 let read_result = read_from_data_source(&reader_config)?;
                 let annotated_word_list = create_word_list(&reader_config, &read_result)?;
 
@@ -110,9 +162,21 @@ pub(super) fn fill_intermediate_entries(
                 Ok(NormalizedEntry {
                     annotated_word_list,
                     stem_algorithm: reader_config.get_stem_algorithm(),
-                    title: stork_file.title.clone(),
-                    url: stork_file.url.clone(),
-                    fields: reader_config.file.fields,
+                    tokenizer: reader_config.get_tokenizer(),
+                    title: if stork_file.title.is_empty() {
+                        derive_title(&read_result).unwrap_or_else(|| stork_file.to_string())
+                    } else {
+                        stork_file.title.clone()
+                    },
+                    url: match stork_file.source() {
+                        DataSource::FilePath(path) if stork_file.url.is_empty() => {
+                            derive_url(&config.input.url_template, &path)
+                        }
+                        DataSource::URL(src_url) if stork_file.url.is_empty() => src_url,
+                        _ => stork_file.url.clone(),
+                    },
+                    fields: read_result.merge_fields(&reader_config.file.fields),
+                    boost: stork_file.boost,
                 })
             }();
 
@@ -147,14 +211,14 @@ pub(super) fn fill_intermediate_entries(
     Ok(())
 }
 
-fn build_progress_bar(config: &Config) -> ProgressBar {
-    let progress_bar = ProgressBar::new((config.input.files.len()).try_into().unwrap()).with_style(
+fn build_progress_bar(files: &[File]) -> ProgressBar {
+    let progress_bar = ProgressBar::new((files.len()).try_into().unwrap()).with_style(
         ProgressStyle::default_bar()
             .template("[{elapsed}] {bar:40.cyan/blue} {pos}/{len} | {msg}")
             .progress_chars("##-"),
     );
 
-    let url_file_count: u32 = config.input.files.iter().fold(0, |acc, file| {
+    let url_file_count: u32 = files.iter().fold(0, |acc, file| {
         if let DataSource::URL(_) = file.source() {
             acc + 1
         } else {
@@ -208,12 +272,125 @@ fn truncate_with_ellipsis_to_length(
 mod tests {
     use super::{fill_intermediate_entries, truncate_with_ellipsis_to_length};
     use crate::{
-        config::{Config, DataSource, File, InputConfig, OutputConfig},
+        config::{
+            Config, DataSource, File, Filetype, FrontmatterConfig, InputConfig, OutputConfig,
+        },
         index_v3::build::{errors::WordListGenerationError, intermediate_entry::NormalizedEntry},
         DocumentError, IndexGenerationError,
     };
+    use std::collections::HashMap;
     use unicode_segmentation::UnicodeSegmentation;
 
+    #[test]
+    fn frontmatter_fields_are_merged_with_config_fields() {
+        let file = File {
+            explicit_source: Some(DataSource::Contents(
+                "---\ndate: 2024-01-01\ncategory: blog\nauthor:\n  name: Jane\n---\n\nHello"
+                    .to_string(),
+            )),
+            filetype: Some(Filetype::Markdown),
+            fields: HashMap::from([("category".to_string(), "news".to_string())]),
+            ..File::default()
+        };
+
+        let input = InputConfig {
+            files: vec![file],
+            frontmatter_handling: FrontmatterConfig::Parse,
+            ..InputConfig::default()
+        };
+
+        let config = Config {
+            input,
+            output: OutputConfig::default(),
+        };
+
+        let mut intermediate_entries: Vec<NormalizedEntry> = vec![];
+        let mut document_errors: Vec<DocumentError> = vec![];
+        fill_intermediate_entries(&config, &mut intermediate_entries, &mut document_errors)
+            .unwrap();
+
+        assert_eq!(
+            intermediate_entries[0].fields,
+            HashMap::from([
+                ("date".to_string(), "2024-01-01".to_string()),
+                ("category".to_string(), "news".to_string()),
+                ("author.name".to_string(), "Jane".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn title_and_url_are_derived_when_not_set() {
+        let input = InputConfig {
+            base_directory: "../test-assets".to_string(),
+            url_template: "/docs/{dir}/{stem}/".to_string(),
+            files: vec![
+                File {
+                    explicit_source: Some(DataSource::FilePath(
+                        "derived-title/getting-started.md".to_string(),
+                    )),
+                    ..File::default()
+                },
+                File {
+                    explicit_source: Some(DataSource::FilePath(
+                        "derived-title/configuration.md".to_string(),
+                    )),
+                    ..File::default()
+                },
+                File {
+                    title: "Set in config".to_string(),
+                    url: "/custom".to_string(),
+                    explicit_source: Some(DataSource::FilePath(
+                        "derived-title/getting-started.md".to_string(),
+                    )),
+                    ..File::default()
+                },
+            ],
+            ..InputConfig::default()
+        };
+
+        let config = Config {
+            input,
+            output: OutputConfig::default(),
+        };
+
+        let mut intermediate_entries: Vec<NormalizedEntry> = vec![];
+        let mut document_errors: Vec<DocumentError> = vec![];
+        fill_intermediate_entries(&config, &mut intermediate_entries, &mut document_errors)
+            .unwrap();
+
+        assert_eq!(intermediate_entries[0].title, "Getting Started");
+        assert_eq!(
+            intermediate_entries[0].url,
+            "/docs/derived-title/getting-started/"
+        );
+        assert_eq!(intermediate_entries[1].title, "Configuration Reference");
+        assert_eq!(intermediate_entries[2].title, "Set in config");
+        assert_eq!(intermediate_entries[2].url, "/custom");
+    }
+
+    #[test]
+    fn file_without_a_source_fails() {
+        let input = InputConfig {
+            files: vec![File::default()],
+            ..InputConfig::default()
+        };
+
+        let config = Config {
+            input,
+            output: OutputConfig::default(),
+        };
+
+        let mut intermediate_entries: Vec<NormalizedEntry> = vec![];
+        let mut document_errors: Vec<DocumentError> = vec![];
+        let _result =
+            fill_intermediate_entries(&config, &mut intermediate_entries, &mut document_errors);
+        assert_eq!(
+            document_errors[0].word_list_generation_error,
+            WordListGenerationError::NoDataSource
+        );
+    }
+
     #[test]
     fn break_on_file_error_breaks() {
         let invalid_file = File {
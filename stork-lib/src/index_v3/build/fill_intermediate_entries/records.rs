@@ -0,0 +1,331 @@
+use serde_json::Value;
+
+use crate::config::{DataSource, File, Filetype, RecordMapping};
+
+use super::data_source_readers::read_text;
+use super::IndexGenerationError;
+
+/**
+ * Expands a file whose source is a JSON feed into one file for each record
+ * in the feed. The feed can be a JSON array of objects, or NDJSON, with one
+ * object on each line.
+ */
+pub(super) fn expand_json_feed(
+    file: &File,
+    feed: &str,
+    base_directory: &str,
+) -> Result<Vec<File>, IndexGenerationError> {
+    let contents = read_text(feed, base_directory)
+        .map_err(|error| IndexGenerationError::FeedNotRead(feed.to_string(), error))?;
+    let invalid_feed =
+        |message: String| IndexGenerationError::InvalidFeed(feed.to_string(), message);
+
+    let records: Vec<Value> = if contents.trim_start().starts_with('[') {
+        serde_json::from_str(&contents).map_err(|error| invalid_feed(error.to_string()))?
+    } else {
+        contents
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| {
+                serde_json::from_str(line)
+                    .map_err(|error| invalid_feed(format!("line {}: {error}", index + 1)))
+            })
+            .collect::<Result<_, _>>()?
+    };
+
+    let mapping = file.mapping.clone().unwrap_or_default();
+
+    records
+        .iter()
+        .enumerate()
+        .map(|(index, record)| {
+            if !record.is_object() {
+                return Err(invalid_feed(format!(
+                    "record {} is not an object",
+                    index + 1
+                )));
+            }
+
+            Ok(record_file(file, &mapping, |key| json_value(record, key)))
+        })
+        .collect()
+}
+
+/**
+ * Expands a file whose source is a CSV or TSV file into one file for each of
+ * its rows. The first row names the columns. Columns that the mapping doesn't
+ * use become fields, named after the column, for rows that have a value in
+ * them.
+ */
+pub(super) fn expand_csv_table(
+    file: &File,
+    table: &str,
+    base_directory: &str,
+) -> Result<Vec<File>, IndexGenerationError> {
+    let contents = read_text(table, base_directory)
+        .map_err(|error| IndexGenerationError::FeedNotRead(table.to_string(), error))?;
+    let invalid_table =
+        |error: csv::Error| IndexGenerationError::InvalidFeed(table.to_string(), error.to_string());
+
+    let delimiter = if table.to_ascii_lowercase().ends_with(".tsv") {
+        b'\t'
+    } else {
+        b','
+    };
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .from_reader(contents.as_bytes());
+
+    let headers = reader.headers().map_err(invalid_table)?.clone();
+    let mapping = file.mapping.clone().unwrap_or_default();
+    let mapped_columns: Vec<&str> = [
+        &mapping.title,
+        &mapping.url,
+        &mapping.contents,
+        &mapping.filetype,
+    ]
+    .into_iter()
+    .chain(mapping.fields.values())
+    .map(String::as_str)
+    .collect();
+
+    reader
+        .records()
+        .map(|row| {
+            let row = row.map_err(invalid_table)?;
+            let cell = |column: &str| {
+                headers
+                    .iter()
+                    .position(|header| header == column)
+                    .and_then(|index| row.get(index))
+                    .map(str::trim)
+                    .filter(|value| !value.is_empty())
+                    .map(ToString::to_string)
+            };
+
+            let mut row_template = file.clone();
+            row_template.fields.extend(
+                headers
+                    .iter()
+                    .filter(|header| !mapped_columns.contains(header))
+                    .filter_map(|header| Some((header.to_string(), cell(header)?))),
+            );
+
+            Ok(record_file(&row_template, &mapping, cell))
+        })
+        .collect()
+}
+
+/**
+ * Builds a file from one record of a feed, using the mapping to find each of
+ * its parts. `get` returns the record's value for a key, if it has one. The
+ * file gets the feed file's fields and overrides, and the mapped fields are
+ * added to the feed file's fields.
+ */
+fn record_file(
+    template: &File,
+    mapping: &RecordMapping,
+    get: impl Fn(&str) -> Option<String>,
+) -> File {
+    let mut fields = template.fields.clone();
+    for (field, key) in &mapping.fields {
+        if let Some(value) = get(key) {
+            fields.insert(field.clone(), value);
+        }
+    }
+
+    File {
+        title: get(&mapping.title).unwrap_or_default(),
+        url: get(&mapping.url).unwrap_or_default(),
+        explicit_source: Some(DataSource::Contents(
+            get(&mapping.contents).unwrap_or_default(),
+        )),
+        id: None,
+        filetype: get(&mapping.filetype)
+            .and_then(|filetype| parse_filetype(&filetype))
+            .or_else(|| template.filetype.clone()),
+        mapping: None,
+        fields,
+        ..template.clone()
+    }
+}
+
+/**
+ * Finds a value in a JSON record by a dotted key like `author.name`. Strings,
+ * numbers, and booleans are returned as strings; nulls, arrays, and objects
+ * aren't returned.
+ */
+fn json_value(record: &Value, key: &str) -> Option<String> {
+    let value = key
+        .split('.')
+        .try_fold(record, |value, part| value.get(part))?;
+
+    match value {
+        Value::String(string) => Some(string.clone()),
+        Value::Number(number) => Some(number.to_string()),
+        Value::Bool(boolean) => Some(boolean.to_string()),
+        _ => None,
+    }
+}
+
+fn parse_filetype(filetype: &str) -> Option<Filetype> {
+    match filetype.trim().to_ascii_lowercase().as_str() {
+        "plaintext" | "text" | "txt" => Some(Filetype::PlainText),
+        "html" | "htm" => Some(Filetype::HTML),
+        "markdown" | "md" => Some(Filetype::Markdown),
+        "srtsubtitle" | "srt" => Some(Filetype::SRTSubtitle),
+        "webvtt" | "vtt" => Some(Filetype::WebVTT),
+        "asciidoc" | "adoc" => Some(Filetype::AsciiDoc),
+        "restructuredtext" | "rst" => Some(Filetype::ReStructuredText),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::collections::{BTreeMap, HashMap};
+
+    fn feed_file(feed: &str, mapping: Option<RecordMapping>) -> File {
+        File {
+            explicit_source: Some(DataSource::JSON(feed.to_string())),
+            fields: HashMap::from([("source".to_string(), "cms".to_string())]),
+            mapping,
+            ..File::default()
+        }
+    }
+
+    #[test]
+    fn ndjson_and_array_feeds_expand_into_the_same_files() {
+        let mapping = RecordMapping {
+            title: "headline".to_string(),
+            url: "permalink".to_string(),
+            contents: "body.html".to_string(),
+            fields: BTreeMap::from([
+                ("author".to_string(), "author.name".to_string()),
+                ("featured".to_string(), "featured".to_string()),
+            ]),
+            ..RecordMapping::default()
+        };
+
+        let ndjson = expand_json_feed(
+            &feed_file("records/articles.ndjson", Some(mapping.clone())),
+            "records/articles.ndjson",
+            "../test-assets",
+        )
+        .unwrap();
+        let array = expand_json_feed(
+            &feed_file("records/articles.json", Some(mapping)),
+            "records/articles.json",
+            "../test-assets",
+        )
+        .unwrap();
+
+        assert_eq!(ndjson, array);
+        assert_eq!(ndjson.len(), 2);
+        assert_eq!(
+            ndjson[0],
+            File {
+                title: "Launch day".to_string(),
+                url: "/news/launch-day/".to_string(),
+                explicit_source: Some(DataSource::Contents(
+                    "<main><p>We launched today.</p></main>".to_string()
+                )),
+                filetype: Some(Filetype::HTML),
+                fields: HashMap::from([
+                    ("source".to_string(), "cms".to_string()),
+                    ("author".to_string(), "Jane".to_string()),
+                    ("featured".to_string(), "true".to_string()),
+                ]),
+                ..File::default()
+            }
+        );
+        assert_eq!(ndjson[1].filetype, Some(Filetype::Markdown));
+        assert_eq!(ndjson[1].fields.get("author"), None);
+    }
+
+    #[test]
+    fn records_use_the_default_mapping() {
+        let file = feed_file("records/default-mapping.ndjson", None);
+        let files =
+            expand_json_feed(&file, "records/default-mapping.ndjson", "../test-assets").unwrap();
+
+        assert_eq!(files[0].title, "Plain");
+        assert_eq!(files[0].url, "/plain/");
+        assert_eq!(
+            files[0].explicit_source,
+            Some(DataSource::Contents("Some plain text".to_string()))
+        );
+        assert_eq!(files[0].filetype, None);
+    }
+
+    #[test]
+    fn csv_and_tsv_rows_expand_into_files() {
+        let mapping = RecordMapping {
+            title: "Question".to_string(),
+            contents: "Answer".to_string(),
+            url: "Link".to_string(),
+            fields: BTreeMap::from([("section".to_string(), "Category".to_string())]),
+            ..RecordMapping::default()
+        };
+        let table_file = |table: &str| File {
+            explicit_source: Some(DataSource::CSV(table.to_string())),
+            mapping: Some(mapping.clone()),
+            ..File::default()
+        };
+
+        let csv = expand_csv_table(
+            &table_file("records/faq.csv"),
+            "records/faq.csv",
+            "../test-assets",
+        )
+        .unwrap();
+        let tsv = expand_csv_table(
+            &table_file("records/faq.tsv"),
+            "records/faq.tsv",
+            "../test-assets",
+        )
+        .unwrap();
+
+        assert_eq!(csv, tsv);
+        assert_eq!(
+            csv[0],
+            File {
+                title: "How do I reset my password?".to_string(),
+                url: "/faq/#reset".to_string(),
+                explicit_source: Some(DataSource::Contents(
+                    "Use the \"Forgot password\" link on the sign-in page.".to_string()
+                )),
+                fields: HashMap::from([
+                    ("section".to_string(), "Accounts".to_string()),
+                    ("Updated".to_string(), "2024-01-10".to_string()),
+                ]),
+                ..File::default()
+            }
+        );
+        assert_eq!(
+            csv[1].fields,
+            HashMap::from([("section".to_string(), "Billing".to_string())])
+        );
+    }
+
+    #[test]
+    fn invalid_feeds_are_errors() {
+        let file = feed_file("records/invalid.ndjson", None);
+        let error =
+            expand_json_feed(&file, "records/invalid.ndjson", "../test-assets").unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("The feed `records/invalid.ndjson` could not be parsed: line 2:"));
+
+        let file = feed_file("records/missing.ndjson", None);
+        assert_eq!(
+            expand_json_feed(&file, "records/missing.ndjson", "../test-assets")
+                .unwrap_err()
+                .to_string(),
+            "The feed `records/missing.ndjson` could not be read: The file `../test-assets/records/missing.ndjson` could not be found."
+        );
+    }
+}
@@ -0,0 +1,277 @@
+use std::collections::HashSet;
+
+use roxmltree::{Document, Node};
+
+use crate::config::{DataSource, File};
+
+use super::data_source_readers::read_text;
+use super::IndexGenerationError;
+
+/**
+ * Expands a file whose source is a sitemap into one file for each page the
+ * sitemap lists. Sitemap indexes are followed to the sitemaps they list.
+ *
+ * Each page is fetched from its `<loc>`, which also becomes its URL, and gets
+ * the sitemap file's fields and overrides. If the page has a `<lastmod>`, it's
+ * stored in the `lastmod` field.
+ */
+pub(super) fn expand_sitemap(
+    file: &File,
+    sitemap: &str,
+    base_directory: &str,
+) -> Result<Vec<File>, IndexGenerationError> {
+    let mut visited_sitemaps = HashSet::new();
+    let mut pages = vec![];
+    collect_pages(sitemap, base_directory, &mut visited_sitemaps, &mut pages)?;
+
+    let mut seen_locs = HashSet::new();
+    let files = pages
+        .into_iter()
+        .filter(|page| seen_locs.insert(page.loc.clone()))
+        .map(|page| {
+            let mut fields = file.fields.clone();
+            if let Some(lastmod) = page.lastmod {
+                fields.insert("lastmod".to_string(), lastmod);
+            }
+
+            File {
+                title: String::new(),
+                url: String::new(),
+                explicit_source: Some(DataSource::URL(page.loc)),
+                id: None,
+                fields,
+                ..file.clone()
+            }
+        })
+        .collect();
+
+    Ok(files)
+}
+
+struct SitemapPage {
+    loc: String,
+    lastmod: Option<String>,
+}
+
+fn collect_pages(
+    sitemap: &str,
+    base_directory: &str,
+    visited_sitemaps: &mut HashSet<String>,
+    pages: &mut Vec<SitemapPage>,
+) -> Result<(), IndexGenerationError> {
+    if !visited_sitemaps.insert(sitemap.to_string()) {
+        return Ok(());
+    }
+
+    let contents = read_text(sitemap, base_directory)
+        .map_err(|error| IndexGenerationError::SitemapNotRead(sitemap.to_string(), error))?;
+    let document = Document::parse(&contents)
+        .map_err(|_| IndexGenerationError::InvalidSitemap(sitemap.to_string()))?;
+    let root = document.root_element();
+
+    match root.tag_name().name() {
+        "urlset" => {
+            pages.extend(child_elements(root, "url").filter_map(|url| {
+                Some(SitemapPage {
+                    loc: child_text(url, "loc")?,
+                    lastmod: child_text(url, "lastmod"),
+                })
+            }));
+        }
+        "sitemapindex" => {
+            for child_sitemap in child_elements(root, "sitemap") {
+                if let Some(loc) = child_text(child_sitemap, "loc") {
+                    collect_pages(&loc, base_directory, visited_sitemaps, pages)?;
+                }
+            }
+        }
+        _ => return Err(IndexGenerationError::InvalidSitemap(sitemap.to_string())),
+    }
+
+    Ok(())
+}
+
+fn child_elements<'a, 'input: 'a>(
+    node: Node<'a, 'input>,
+    name: &'a str,
+) -> impl Iterator<Item = Node<'a, 'input>> + 'a {
+    node.children()
+        .filter(move |child| child.is_element() && child.tag_name().name() == name)
+}
+
+fn child_text(node: Node, name: &str) -> Option<String> {
+    child_elements(node, name)
+        .next()
+        .and_then(|child| child.text())
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+        .map(ToString::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{config::StemmingConfig, index_v3::build::errors::WordListGenerationError};
+    use pretty_assertions::assert_eq;
+    use std::collections::HashMap;
+    use std::path::Path;
+
+    fn sitemap_file(sitemap: &str) -> File {
+        File {
+            explicit_source: Some(DataSource::Sitemap(sitemap.to_string())),
+            stemming_override: Some(StemmingConfig::None),
+            fields: HashMap::from([("section".to_string(), "docs".to_string())]),
+            ..File::default()
+        }
+    }
+
+    #[test]
+    fn sitemap_index_expands_into_pages() {
+        let file = sitemap_file("sitemap/sitemap-index.xml");
+        let files = expand_sitemap(&file, "sitemap/sitemap-index.xml", "../test-assets").unwrap();
+
+        assert_eq!(
+            files.iter().map(File::source).collect::<Vec<DataSource>>(),
+            vec![
+                DataSource::URL("https://example.com/".to_string()),
+                DataSource::URL("https://example.com/docs/install/".to_string()),
+                DataSource::URL("https://example.com/blog/hello/".to_string()),
+            ]
+        );
+
+        assert_eq!(
+            files[1].fields,
+            HashMap::from([
+                ("section".to_string(), "docs".to_string()),
+                ("lastmod".to_string(), "2024-03-01".to_string()),
+            ])
+        );
+        assert_eq!(files[0].fields.get("lastmod"), None);
+        assert_eq!(files[2].stemming_override, Some(StemmingConfig::None));
+        assert!(files.iter().all(|file| file.url.is_empty()));
+    }
+
+    #[test]
+    fn unreadable_and_invalid_sitemaps_are_errors() {
+        let file = sitemap_file("sitemap/missing.xml");
+        assert!(matches!(
+            expand_sitemap(&file, "sitemap/missing.xml", "../test-assets").unwrap_err(),
+            IndexGenerationError::SitemapNotRead(sitemap, WordListGenerationError::FileNotFound(path))
+                if sitemap == "sitemap/missing.xml"
+                    && path == Path::new("../test-assets/sitemap/missing.xml")
+        ));
+
+        let file = sitemap_file("federalist.toml");
+        assert!(matches!(
+            expand_sitemap(&file, "federalist.toml", "../test-assets").unwrap_err(),
+            IndexGenerationError::InvalidSitemap(sitemap) if sitemap == "federalist.toml"
+        ));
+    }
+
+    /**
+     * Serves some paths over HTTP on a local port, standing in for a deployed
+     * site. `routes` is given the server's origin, like
+     * `http://127.0.0.1:1234`, and returns each path with its content type
+     * and body.
+     */
+    #[cfg(feature = "build-v3-web-scraping")]
+    fn serve(routes: impl FnOnce(&str) -> Vec<(&'static str, &'static str, String)>) -> String {
+        use std::io::{BufRead, BufReader, Write};
+        use std::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let origin = format!("http://{}", listener.local_addr().unwrap());
+        let routes = routes(&origin);
+
+        std::thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                let _ = reader.read_line(&mut request_line);
+
+                let mut header = String::new();
+                while reader.read_line(&mut header).unwrap_or(0) > 2 {
+                    header.clear();
+                }
+
+                let path = request_line.split_whitespace().nth(1).unwrap_or_default();
+                let response = match routes.iter().find(|(route, ..)| *route == path) {
+                    Some((_, content_type, body)) => format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                        body.len()
+                    ),
+                    None => "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                        .to_string(),
+                };
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+
+        origin
+    }
+
+    #[test]
+    #[cfg(feature = "build-v3-web-scraping")]
+    fn remote_sitemap_pages_are_fetched_and_indexed() {
+        use crate::config::{Config, InputConfig};
+        use crate::index_v3::build::fill_intermediate_entries::fill_intermediate_entries;
+
+        let origin = serve(|origin| {
+            vec![
+                (
+                    "/sitemap.xml",
+                    "application/xml",
+                    format!(
+                        r#"<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+                            <url><loc>{origin}/install/</loc><lastmod>2024-03-01</lastmod></url>
+                            <url><loc>{origin}/about/</loc></url>
+                        </urlset>"#
+                    ),
+                ),
+                (
+                    "/install/",
+                    "text/html",
+                    "<html><head><title>Installing</title></head><body><main>Run the installer</main></body></html>"
+                        .to_string(),
+                ),
+                (
+                    "/about/",
+                    "text/html",
+                    "<html><body><main><h1>About</h1><p>Who we are</p></main></body></html>".to_string(),
+                ),
+            ]
+        });
+
+        let config = Config {
+            input: InputConfig {
+                files: vec![File {
+                    explicit_source: Some(DataSource::Sitemap(format!("{origin}/sitemap.xml"))),
+                    ..File::default()
+                }],
+                ..InputConfig::default()
+            },
+            ..Config::default()
+        };
+
+        let mut intermediate_entries = vec![];
+        let mut document_errors = vec![];
+        fill_intermediate_entries(&config, &mut intermediate_entries, &mut document_errors)
+            .unwrap();
+
+        assert_eq!(document_errors, vec![]);
+        assert_eq!(
+            intermediate_entries
+                .iter()
+                .map(|entry| (entry.title.as_str(), entry.url.clone()))
+                .collect::<Vec<(&str, String)>>(),
+            vec![
+                ("Installing", format!("{origin}/install/")),
+                ("About", format!("{origin}/about/")),
+            ]
+        );
+        assert_eq!(
+            intermediate_entries[0].fields.get("lastmod"),
+            Some(&"2024-03-01".to_string())
+        );
+    }
+}
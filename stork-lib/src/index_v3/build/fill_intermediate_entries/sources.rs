@@ -0,0 +1,277 @@
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
+
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use walkdir::WalkDir;
+
+use crate::config::{DataSource, File, InputConfig, SourceConfig};
+
+use super::crawl::expand_crawl;
+use super::data_source_readers::filepath_data_source_reader::get_filetype_from_path;
+use super::records::{expand_csv_table, expand_json_feed};
+use super::sitemap::expand_sitemap;
+use super::IndexGenerationError;
+
+/**
+ * Returns the files listed in the config, with sitemaps, crawls, and feeds
+ * expanded into the files they list, followed by the files found by walking
+ * each of the config's sources. Files found by a source are sorted by path,
+ * and files that are already listed explicitly aren't added again.
+ */
+pub(super) fn expand_sources(config: &InputConfig) -> Result<Vec<File>, IndexGenerationError> {
+    let mut files = vec![];
+    for file in &config.files {
+        match &file.explicit_source {
+            Some(DataSource::Sitemap(sitemap)) => {
+                files.extend(expand_sitemap(file, sitemap, &config.base_directory)?);
+            }
+            Some(DataSource::Crawl(start)) => {
+                files.extend(expand_crawl(file, start, &config.base_directory)?);
+            }
+            Some(DataSource::JSON(feed)) => {
+                files.extend(expand_json_feed(file, feed, &config.base_directory)?);
+            }
+            Some(DataSource::CSV(table)) => {
+                files.extend(expand_csv_table(file, table, &config.base_directory)?);
+            }
+            _ => files.push(file.clone()),
+        }
+    }
+
+    let mut seen_paths: HashSet<String> = config
+        .files
+        .iter()
+        .filter_map(|file| match &file.explicit_source {
+            Some(DataSource::FilePath(path)) => Some(normalize_path(path)),
+            _ => None,
+        })
+        .collect();
+
+    for source in &config.sources {
+        for path in find_source_paths(&config.base_directory, source)? {
+            if seen_paths.insert(path.clone()) {
+                files.push(File {
+                    explicit_source: Some(DataSource::FilePath(path)),
+                    stemming_override: source.stemming_override.clone(),
+                    tokenizer_override: source.tokenizer_override,
+                    html_selector_override: source.html_selector_override.clone(),
+                    exclude_html_selector_override: source.exclude_html_selector_override.clone(),
+                    frontmatter_handling_override: source.frontmatter_handling_override.clone(),
+                    filetype: source.filetype.clone(),
+                    boost: source.boost,
+                    ..File::default()
+                });
+            }
+        }
+    }
+
+    Ok(files)
+}
+
+/**
+ * Walks the source's directory and returns the paths, relative to the base
+ * directory, of the files that match its include and exclude patterns.
+ */
+fn find_source_paths(
+    base_directory: &str,
+    source: &SourceConfig,
+) -> Result<Vec<String>, IndexGenerationError> {
+    let include = build_glob_set(&source.include)?;
+    let exclude = build_glob_set(&source.exclude)?;
+
+    let root = Path::new(base_directory).join(&source.directory);
+    if !root.is_dir() {
+        return Err(IndexGenerationError::SourceDirectoryNotFound(root));
+    }
+
+    let paths = WalkDir::new(&root)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| {
+            let relative_path = entry.path().strip_prefix(&root).ok()?;
+            let relative_path_string = normalize_path(relative_path.to_str()?);
+
+            let is_included =
+                include.is_match(&relative_path_string) && !exclude.is_match(&relative_path_string);
+            let has_filetype =
+                source.filetype.is_some() || get_filetype_from_path(relative_path).is_some();
+
+            if is_included && has_filetype {
+                let path: PathBuf = Path::new(&source.directory).join(relative_path);
+                path.to_str().map(normalize_path)
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    Ok(paths)
+}
+
+fn build_glob_set(patterns: &[String]) -> Result<GlobSet, IndexGenerationError> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob = GlobBuilder::new(pattern)
+            .literal_separator(true)
+            .build()
+            .map_err(|_| IndexGenerationError::InvalidSourcePattern(pattern.clone()))?;
+        builder.add(glob);
+    }
+
+    builder
+        .build()
+        .map_err(|_| IndexGenerationError::InvalidSourcePattern(patterns.join(", ")))
+}
+
+fn normalize_path(path: &str) -> String {
+    path.replace('\\', "/").trim_start_matches("./").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{Filetype, StemmingConfig};
+    use pretty_assertions::assert_eq;
+
+    fn source_paths(files: &[File]) -> Vec<String> {
+        files
+            .iter()
+            .map(|file| match &file.explicit_source {
+                Some(DataSource::FilePath(path)) => path.clone(),
+                _ => String::new(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn sources_expand_into_files_with_their_overrides() {
+        let config = InputConfig {
+            base_directory: "../test-assets".to_string(),
+            sources: vec![SourceConfig {
+                directory: "derived-title".to_string(),
+                include: vec!["**/*.md".to_string()],
+                stemming_override: Some(StemmingConfig::None),
+                ..SourceConfig::default()
+            }],
+            ..InputConfig::default()
+        };
+
+        let files = expand_sources(&config).unwrap();
+        assert_eq!(
+            source_paths(&files),
+            vec![
+                "derived-title/configuration.md",
+                "derived-title/getting-started.md"
+            ]
+        );
+        assert_eq!(files[0].stemming_override, Some(StemmingConfig::None));
+        assert!(files[0].title.is_empty());
+        assert!(files[0].url.is_empty());
+    }
+
+    #[test]
+    fn excluded_and_explicit_files_are_not_added() {
+        let config = InputConfig {
+            base_directory: "../test-assets".to_string(),
+            files: vec![File {
+                explicit_source: Some(DataSource::FilePath(
+                    "./derived-title/getting-started.md".to_string(),
+                )),
+                title: "Explicit".to_string(),
+                ..File::default()
+            }],
+            sources: vec![SourceConfig {
+                directory: "derived-title".to_string(),
+                exclude: vec!["configuration.*".to_string()],
+                ..SourceConfig::default()
+            }],
+            ..InputConfig::default()
+        };
+
+        let files = expand_sources(&config).unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].title, "Explicit");
+    }
+
+    #[test]
+    fn single_star_does_not_cross_directories() {
+        let config_with_include = |include: &str| InputConfig {
+            base_directory: "../test-assets".to_string(),
+            sources: vec![SourceConfig {
+                include: vec![include.to_string()],
+                ..SourceConfig::default()
+            }],
+            ..InputConfig::default()
+        };
+
+        assert!(expand_sources(&config_with_include("*.md"))
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            expand_sources(&config_with_include("**/*.md"))
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn files_with_unknown_extensions_need_a_filetype() {
+        let config_with_filetype = |filetype: Option<Filetype>| InputConfig {
+            base_directory: "../test-assets".to_string(),
+            sources: vec![SourceConfig {
+                include: vec!["*".to_string()],
+                filetype,
+                ..SourceConfig::default()
+            }],
+            ..InputConfig::default()
+        };
+
+        assert!(expand_sources(&config_with_filetype(None))
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            source_paths(
+                &expand_sources(&config_with_filetype(Some(Filetype::PlainText))).unwrap()
+            ),
+            vec![
+                "federalist-min-0.5.3.st",
+                "federalist-min-0.6.0.st",
+                "federalist-min-0.7.0.st",
+                "federalist.toml"
+            ]
+        );
+    }
+
+    #[test]
+    fn missing_directories_and_bad_patterns_are_errors() {
+        let missing_directory = InputConfig {
+            sources: vec![SourceConfig {
+                directory: "does-not-exist".to_string(),
+                ..SourceConfig::default()
+            }],
+            ..InputConfig::default()
+        };
+        assert!(matches!(
+            expand_sources(&missing_directory).unwrap_err(),
+            IndexGenerationError::SourceDirectoryNotFound(path) if path == Path::new("does-not-exist")
+        ));
+
+        let bad_pattern = InputConfig {
+            base_directory: "../test-assets".to_string(),
+            sources: vec![SourceConfig {
+                include: vec!["[unclosed".to_string()],
+                ..SourceConfig::default()
+            }],
+            ..InputConfig::default()
+        };
+        assert!(matches!(
+            expand_sources(&bad_pattern).unwrap_err(),
+            IndexGenerationError::InvalidSourcePattern(pattern) if pattern == "[unclosed"
+        ));
+    }
+}
@@ -0,0 +1,369 @@
+use std::collections::HashSet;
+
+use crate::index_v3::AnnotatedWordList;
+
+use super::{word_list_from_sections, ReadResult, ReaderConfig, Section, WordListGenerationError};
+
+#[allow(clippy::unnecessary_wraps)]
+pub fn generate(
+    config: &ReaderConfig,
+    read_result: &ReadResult,
+) -> Result<AnnotatedWordList, WordListGenerationError> {
+    Ok(word_list_from_sections(
+        config,
+        &parse_sections(&read_result.buffer),
+    ))
+}
+
+/**
+ * Splits an `.adoc` document into its sections, keeping the text a reader
+ * would see. Each section's anchor is the ID set with `[[id]]` or `[#id]`
+ * above its title, or else the ID Asciidoctor generates from the title.
+ *
+ * Comments, attribute entries, block attribute lines, and block delimiters
+ * are left out, and inline formatting and macros are reduced to their text.
+ */
+fn parse_sections(buffer: &str) -> Vec<Section> {
+    let mut sections = vec![Section {
+        anchor: None,
+        text: String::new(),
+    }];
+    let mut used_ids = HashSet::new();
+    let mut pending_anchor: Option<String> = None;
+    let mut is_in_comment_block = false;
+
+    for line in buffer.lines().map(str::trim_end) {
+        if line == "////" {
+            is_in_comment_block = !is_in_comment_block;
+            continue;
+        }
+
+        if is_in_comment_block || line.starts_with("//") || is_attribute_entry(line) {
+            continue;
+        }
+
+        if let Some(id) = block_anchor(line) {
+            pending_anchor = Some(id);
+            continue;
+        }
+
+        if (line.starts_with('[') && line.ends_with(']')) || is_block_delimiter(line) {
+            continue;
+        }
+
+        if let Some((level, title)) = section_title(line) {
+            let title = strip_inline_markup(title);
+            let anchor = pending_anchor.take();
+
+            if level == 0 {
+                push_line(sections.last_mut().unwrap(), &title);
+            } else {
+                let anchor = anchor.unwrap_or_else(|| generate_id(&title, &used_ids));
+                used_ids.insert(anchor.clone());
+                sections.push(Section {
+                    anchor: Some(anchor),
+                    text: String::new(),
+                });
+                push_line(sections.last_mut().unwrap(), &title);
+            }
+            continue;
+        }
+
+        pending_anchor = None;
+        let text = strip_line_prefix(line);
+        push_line(sections.last_mut().unwrap(), &strip_inline_markup(text));
+    }
+
+    sections
+}
+
+fn push_line(section: &mut Section, line: &str) {
+    if !line.is_empty() {
+        section.text.push_str(line);
+        section.text.push('\n');
+    }
+}
+
+fn is_attribute_entry(line: &str) -> bool {
+    line.strip_prefix(':')
+        .and_then(|rest| rest.split_once(':'))
+        .is_some_and(|(name, _)| {
+            !name.is_empty()
+                && name
+                    .trim_end_matches('!')
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+        })
+}
+
+/**
+ * Reads the ID out of a block anchor line like `[[install]]`,
+ * `[[install,Installing]]`, or `[#install.role]`.
+ */
+fn block_anchor(line: &str) -> Option<String> {
+    let id = if let Some(inner) = line.strip_prefix("[[").and_then(|l| l.strip_suffix("]]")) {
+        inner.split(',').next()?
+    } else {
+        let inner = line.strip_prefix("[#")?.strip_suffix(']')?;
+        inner.split(['.', '%', ',']).next()?
+    };
+
+    let id = id.trim();
+    if id.is_empty() || id.contains(char::is_whitespace) {
+        None
+    } else {
+        Some(id.to_string())
+    }
+}
+
+/**
+ * Whether a line opens or closes a delimited block, like `----` around a
+ * listing, `====` around an example, or `|===` around a table.
+ */
+fn is_block_delimiter(line: &str) -> bool {
+    if line == "--" || line == "|===" {
+        return true;
+    }
+
+    let mut chars = line.chars();
+    match chars.next() {
+        Some(first) if "-.*=_+/".contains(first) => line.len() >= 4 && chars.all(|c| c == first),
+        _ => false,
+    }
+}
+
+/**
+ * Reads a section title like `== Installing`, returning its level (0 for the
+ * document title) and its text.
+ */
+fn section_title(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|c| *c == '=').count();
+    let title = line[level..].strip_prefix(' ')?.trim();
+    if (1..=6).contains(&level) && !title.is_empty() {
+        Some((level - 1, title))
+    } else {
+        None
+    }
+}
+
+/**
+ * Removes the markers at the start of a line that aren't part of its text:
+ * list markers, block title dots, and admonition labels like `NOTE:`.
+ */
+fn strip_line_prefix(line: &str) -> &str {
+    let trimmed = line.trim_start();
+    let without_list_marker = trimmed
+        .trim_start_matches(['*', '.', '-'])
+        .strip_prefix(' ')
+        .unwrap_or(trimmed);
+    let without_block_title = if without_list_marker == trimmed {
+        trimmed
+            .strip_prefix('.')
+            .filter(|rest| !rest.starts_with(['.', ' ']))
+            .unwrap_or(trimmed)
+    } else {
+        without_list_marker
+    };
+
+    ["NOTE: ", "TIP: ", "IMPORTANT: ", "WARNING: ", "CAUTION: "]
+        .iter()
+        .find_map(|label| without_block_title.strip_prefix(label))
+        .unwrap_or(without_block_title)
+}
+
+/**
+ * Reduces inline markup to its text: cross references like `<<id,text>>`
+ * and macros like `link:url[text]` become their text, and formatting marks
+ * like `*strong*`, `_emphasis_`, and backticks are removed.
+ */
+fn strip_inline_markup(text: &str) -> String {
+    let text = replace_cross_references(text);
+    let text = replace_macros(&text);
+
+    text.split(' ')
+        .map(|word| {
+            let word = word.replace(['*', '`'], "");
+            let end_of_word = word.trim_end_matches(|c: char| ".,;:!?)".contains(c));
+            let trailing_punctuation = &word[end_of_word.len()..];
+            format!(
+                "{}{}",
+                end_of_word.trim_matches(['_', '#']),
+                trailing_punctuation
+            )
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+        .trim()
+        .to_string()
+}
+
+fn replace_cross_references(text: &str) -> String {
+    let mut output = String::new();
+    let mut rest = text;
+
+    while let Some(start) = rest.find("<<") {
+        let Some(length) = rest[start..].find(">>") else {
+            break;
+        };
+        let inner = &rest[start + 2..start + length];
+        output.push_str(&rest[..start]);
+        output.push_str(inner.split_once(',').map_or(inner, |(_, text)| text.trim()));
+        rest = &rest[start + length + 2..];
+    }
+
+    output.push_str(rest);
+    output
+}
+
+/**
+ * Replaces macros like `https://example.com[Example]` and `xref:install.adoc[]`
+ * with their text, or with their target if they have no text.
+ */
+fn replace_macros(text: &str) -> String {
+    let mut output = String::new();
+    let mut rest = text;
+
+    while let Some(open) = rest.find('[') {
+        let before = &rest[..open];
+        let target_start = before
+            .rfind(char::is_whitespace)
+            .map_or(0, |index| index + 1);
+        let target = &before[target_start..];
+
+        match rest[open..].find(']') {
+            Some(length) if target.contains(':') => {
+                let macro_text = &rest[open + 1..open + length];
+                let macro_text = macro_text.split(',').next().unwrap_or_default();
+                output.push_str(&before[..target_start]);
+                let macro_target = match target.split_once(':') {
+                    Some((_, path)) if !path.starts_with("//") => path,
+                    _ => target,
+                };
+                output.push_str(if macro_text.is_empty() {
+                    macro_target
+                } else {
+                    macro_text.trim_end_matches('^')
+                });
+                rest = &rest[open + length + 1..];
+            }
+            _ => {
+                output.push_str(&rest[..=open]);
+                rest = &rest[open + 1..];
+            }
+        }
+    }
+
+    output.push_str(rest);
+    output
+}
+
+/**
+ * Generates a section ID the way Asciidoctor does by default: the title in
+ * lowercase, with an underscore in front, and spaces, hyphens, and periods
+ * turned into underscores. Repeated IDs get a number on the end.
+ */
+fn generate_id(title: &str, used_ids: &HashSet<String>) -> String {
+    let mut id = String::from("_");
+    for c in title.to_lowercase().chars() {
+        if c.is_alphanumeric() {
+            id.push(c);
+        } else if (c == ' ' || c == '-' || c == '.' || c == '_') && !id.ends_with('_') {
+            id.push('_');
+        }
+    }
+    let id = id.trim_end_matches('_').to_string();
+
+    let mut unique_id = id.clone();
+    let mut number = 2;
+    while used_ids.contains(&unique_id) {
+        unique_id = format!("{id}_{number}");
+        number += 1;
+    }
+    unique_id
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn section(anchor: Option<&str>, text: &str) -> Section {
+        Section {
+            anchor: anchor.map(ToString::to_string),
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn sections_get_explicit_or_generated_anchors() {
+        let asciidoc = r"= User Guide
+:toc: left
+:source-highlighter!:
+
+Welcome to the *guide*.
+
+== Getting Started
+
+NOTE: Read this first.
+
+[[install-steps]]
+== Installing Stork
+
+. Download the `stork` binary.
+. See <<_getting_started,the start>> or https://stork-search.net[the site^].
+
+// a comment
+////
+A comment block
+////
+
+[source,shell]
+----
+stork build
+----
+
+== Getting Started
+";
+
+        assert_eq!(
+            parse_sections(asciidoc),
+            vec![
+                section(None, "User Guide\nWelcome to the guide.\n"),
+                section(
+                    Some("_getting_started"),
+                    "Getting Started\nRead this first.\n"
+                ),
+                section(
+                    Some("install-steps"),
+                    "Installing Stork\nDownload the stork binary.\nSee the start or the site.\nstork build\n"
+                ),
+                section(Some("_getting_started_2"), "Getting Started\n"),
+            ]
+        );
+    }
+
+    #[test]
+    fn ids_are_generated_like_asciidoctor() {
+        let used_ids = HashSet::from(["_faq".to_string()]);
+        assert_eq!(
+            generate_id("What's new in v1.2?", &used_ids),
+            "_whats_new_in_v1_2"
+        );
+        assert_eq!(
+            generate_id("Snake_case - Title", &used_ids),
+            "_snake_case_title"
+        );
+        assert_eq!(generate_id("FAQ", &used_ids), "_faq_2");
+    }
+
+    #[test]
+    fn block_anchors_can_be_written_either_way() {
+        assert_eq!(block_anchor("[[install]]"), Some("install".to_string()));
+        assert_eq!(
+            block_anchor("[[install,Installing]]"),
+            Some("install".to_string())
+        );
+        assert_eq!(block_anchor("[#install.lead]"), Some("install".to_string()));
+        assert_eq!(block_anchor("[source,rust]"), None);
+    }
+}
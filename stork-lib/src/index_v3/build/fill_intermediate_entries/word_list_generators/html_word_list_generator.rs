@@ -1,5 +1,5 @@
 use crate::{
-    index_v3::{AnnotatedWord, AnnotatedWordList},
+    index_v3::{tokenizer::split_into_annotated_words, AnnotatedWord, AnnotatedWordList},
     InternalWordAnnotation,
 };
 
@@ -94,24 +94,24 @@ pub fn generate(
                     })();
 
                     if !contents.is_empty() {
-                        let mut annotated_words: Vec<AnnotatedWord> = contents
-                            .split_whitespace()
-                            .map(ToString::to_string)
-                            .map(|word| AnnotatedWord {
-                                word,
-                                internal_annotations: {
-                                    if let Some(latest_id) = latest_id.clone() {
-                                        vec![InternalWordAnnotation::UrlSuffix(format!(
-                                            "#{}",
-                                            latest_id
-                                        ))]
-                                    } else {
-                                        vec![]
-                                    }
-                                },
-                                fields: HashMap::default(),
-                            })
-                            .collect();
+                        let mut annotated_words: Vec<AnnotatedWord> =
+                            split_into_annotated_words(&contents, config.get_tokenizer())
+                                .into_iter()
+                                .map(|annotated_word| AnnotatedWord {
+                                    internal_annotations: {
+                                        if let Some(latest_id) = latest_id.clone() {
+                                            vec![InternalWordAnnotation::UrlSuffix(format!(
+                                                "#{}",
+                                                latest_id
+                                            ))]
+                                        } else {
+                                            vec![]
+                                        }
+                                    },
+                                    fields: HashMap::default(),
+                                    ..annotated_word
+                                })
+                                .collect();
 
                         word_list.append(&mut annotated_words);
                     }
@@ -158,6 +158,7 @@ mod tests {
             buffer: str.to_string(),
             filetype: Some(Filetype::HTML),
             frontmatter_fields: None,
+            frontmatter_title: None,
         }
     }
 
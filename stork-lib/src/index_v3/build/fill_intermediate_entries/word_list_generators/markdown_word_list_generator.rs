@@ -16,6 +16,7 @@ pub fn generate(
         buffer: html_string,
         filetype: read_result.filetype.clone(),
         frontmatter_fields: None,
+        frontmatter_title: None,
     };
     html_word_list_generator::generate(config, &html_read_result)
 }
@@ -46,6 +47,7 @@ mod tests {
                 buffer: markdown_content.to_string(),
                 filetype: Some(Filetype::Markdown),
                 frontmatter_fields: None,
+                frontmatter_title: None,
             },
         )
         .unwrap()
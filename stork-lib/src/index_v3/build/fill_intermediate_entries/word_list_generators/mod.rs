@@ -1,11 +1,18 @@
-use crate::{config::Filetype, index_v3::AnnotatedWordList};
+use crate::{
+    config::Filetype,
+    index_v3::{tokenizer::split_into_annotated_words, AnnotatedWord, AnnotatedWordList},
+    InternalWordAnnotation,
+};
 
 use super::{ReadResult, ReaderConfig, WordListGenerationError};
 
+pub mod asciidoc_word_list_generator;
 pub mod html_word_list_generator;
 pub mod markdown_word_list_generator;
 pub mod plaintext_word_list_generator;
+pub mod restructuredtext_word_list_generator;
 pub mod srt_word_list_generator;
+pub mod webvtt_word_list_generator;
 
 pub(super) fn create_word_list(
     config: &ReaderConfig,
@@ -14,8 +21,51 @@ pub(super) fn create_word_list(
     match read_result.filetype {
         Some(Filetype::PlainText) => plaintext_word_list_generator::generate(config, read_result),
         Some(Filetype::SRTSubtitle) => srt_word_list_generator::generate(config, read_result),
+        Some(Filetype::WebVTT) => webvtt_word_list_generator::generate(config, read_result),
         Some(Filetype::HTML) => html_word_list_generator::generate(config, read_result),
         Some(Filetype::Markdown) => markdown_word_list_generator::generate(config, read_result),
+        Some(Filetype::AsciiDoc) => asciidoc_word_list_generator::generate(config, read_result),
+        Some(Filetype::ReStructuredText) => {
+            restructuredtext_word_list_generator::generate(config, read_result)
+        }
         None => Err(WordListGenerationError::CannotDetermineFiletype),
     }
 }
+
+/**
+ * A run of a document's text, with the anchor of the section it's in. The
+ * generators for markup languages other than HTML split documents into these.
+ */
+#[derive(Debug, PartialEq)]
+struct Section {
+    anchor: Option<String>,
+    text: String,
+}
+
+/**
+ * Builds a word list from a document's sections. Like the HTML generator does
+ * with element IDs, each word links to its section's anchor if the config
+ * asks for the nearest ID to be saved.
+ */
+fn word_list_from_sections(config: &ReaderConfig, sections: &[Section]) -> AnnotatedWordList {
+    let word_list = sections
+        .iter()
+        .flat_map(|section| {
+            let internal_annotations = match &section.anchor {
+                Some(anchor) if config.output.save_nearest_html_id => {
+                    vec![InternalWordAnnotation::UrlSuffix(format!("#{anchor}"))]
+                }
+                _ => vec![],
+            };
+
+            split_into_annotated_words(&section.text, config.get_tokenizer())
+                .into_iter()
+                .map(move |annotated_word| AnnotatedWord {
+                    internal_annotations: internal_annotations.clone(),
+                    ..annotated_word
+                })
+        })
+        .collect();
+
+    AnnotatedWordList { word_list }
+}
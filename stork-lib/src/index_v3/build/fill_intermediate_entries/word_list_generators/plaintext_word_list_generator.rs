@@ -4,10 +4,12 @@ use super::{ReadResult, ReaderConfig, WordListGenerationError};
 
 #[allow(clippy::unnecessary_wraps)]
 pub fn generate(
-    _config: &ReaderConfig,
+    config: &ReaderConfig,
     read_result: &ReadResult,
 ) -> Result<AnnotatedWordList, WordListGenerationError> {
     Ok(AnnotatedWordList {
-        word_list: read_result.buffer.make_annotated_words(),
+        word_list: read_result
+            .buffer
+            .make_annotated_words(config.get_tokenizer()),
     })
 }
@@ -0,0 +1,437 @@
+use std::collections::HashSet;
+
+use unicode_normalization::UnicodeNormalization;
+
+use crate::index_v3::AnnotatedWordList;
+
+use super::{word_list_from_sections, ReadResult, ReaderConfig, Section, WordListGenerationError};
+
+/// Directives whose content isn't text a reader would see on the page.
+const SKIPPED_DIRECTIVES: [&str; 6] = [
+    "toctree",
+    "image",
+    "include",
+    "literalinclude",
+    "raw",
+    "meta",
+];
+
+#[allow(clippy::unnecessary_wraps)]
+pub fn generate(
+    config: &ReaderConfig,
+    read_result: &ReadResult,
+) -> Result<AnnotatedWordList, WordListGenerationError> {
+    Ok(word_list_from_sections(
+        config,
+        &parse_sections(&read_result.buffer),
+    ))
+}
+
+/**
+ * Splits a reStructuredText document into its sections, keeping the text a
+ * reader would see. Each section's anchor is the label of a `.. _label:`
+ * target above its title, or else the ID docutils generates from the title.
+ *
+ * Comments, field lists, directive options, and the contents of directives
+ * like `toctree` are left out, and inline markup is reduced to its text.
+ */
+fn parse_sections(buffer: &str) -> Vec<Section> {
+    let lines: Vec<&str> = buffer.lines().map(str::trim_end).collect();
+    let mut sections = vec![Section {
+        anchor: None,
+        text: String::new(),
+    }];
+    let mut used_ids = HashSet::new();
+    let mut pending_label: Option<String> = None;
+    let mut is_in_skipped_block = false;
+    let mut index = 0;
+
+    while index < lines.len() {
+        let line = lines[index];
+
+        if is_in_skipped_block {
+            if line.is_empty() || line.starts_with(char::is_whitespace) {
+                index += 1;
+                continue;
+            }
+            is_in_skipped_block = false;
+        }
+
+        if let Some((title, title_line_count)) = section_title_at(&lines, index) {
+            let title = strip_inline_markup(title);
+            let anchor = pending_label
+                .take()
+                .unwrap_or_else(|| generate_id(&title, &used_ids));
+            used_ids.insert(anchor.clone());
+            sections.push(Section {
+                anchor: Some(anchor),
+                text: String::new(),
+            });
+            push_line(sections.last_mut().unwrap(), &title);
+            index += title_line_count;
+            continue;
+        }
+
+        index += 1;
+        let trimmed = line.trim_start();
+
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        if is_adornment(trimmed) || is_field(trimmed) {
+            continue;
+        }
+
+        if let Some(markup) = trimmed
+            .strip_prefix("..")
+            .filter(|rest| rest.is_empty() || rest.starts_with(' '))
+        {
+            let markup = markup.trim();
+            if let Some(label) = markup.strip_prefix('_').and_then(|l| l.strip_suffix(':')) {
+                pending_label = Some(generate_id(label, &HashSet::new()));
+            } else if let Some((directive, _argument)) = markup.split_once("::") {
+                is_in_skipped_block = SKIPPED_DIRECTIVES.contains(&directive.trim());
+            } else if let Some((_, footnote)) = markup
+                .strip_prefix('[')
+                .and_then(|rest| rest.split_once(']'))
+            {
+                push_line(sections.last_mut().unwrap(), &strip_inline_markup(footnote));
+            } else {
+                is_in_skipped_block = true;
+            }
+            continue;
+        }
+
+        pending_label = None;
+        let text = strip_list_marker(trimmed);
+        let text = match text.strip_suffix("::") {
+            Some(before) if before.ends_with(' ') || before.is_empty() => before.trim_end(),
+            Some(before) => &text[..=before.len()],
+            None => text,
+        };
+        push_line(sections.last_mut().unwrap(), &strip_inline_markup(text));
+    }
+
+    sections
+}
+
+fn push_line(section: &mut Section, line: &str) {
+    if !line.is_empty() {
+        section.text.push_str(line);
+        section.text.push('\n');
+    }
+}
+
+/**
+ * Whether a line is made of one punctuation character repeated, like the
+ * `=====` under a section title or a `----` transition.
+ */
+fn is_adornment(line: &str) -> bool {
+    let mut chars = line.chars();
+    match chars.next() {
+        Some(first) if first.is_ascii_punctuation() => line.len() >= 3 && chars.all(|c| c == first),
+        _ => false,
+    }
+}
+
+/**
+ * Reads the section title that starts at a line, if there is one, returning
+ * its text and how many lines it takes up. Titles are underlined, and
+ * optionally overlined with the same adornment.
+ */
+fn section_title_at<'a>(lines: &[&'a str], index: usize) -> Option<(&'a str, usize)> {
+    let line = lines[index];
+    let next_line = lines.get(index + 1)?;
+
+    if is_adornment(line) {
+        let title = next_line.trim();
+        let underline = lines.get(index + 2)?;
+        return (!title.is_empty() && *underline == line).then_some((title, 3));
+    }
+
+    let is_title =
+        !line.is_empty() && !line.starts_with(char::is_whitespace) && is_adornment(next_line);
+    is_title.then_some((line, 2))
+}
+
+/**
+ * Whether a line is a field, like `:author: Jane` in a document's
+ * bibliographic fields or `:maxdepth: 2` in a directive's options.
+ */
+fn is_field(line: &str) -> bool {
+    line.strip_prefix(':')
+        .and_then(|rest| rest.split_once(':'))
+        .is_some_and(|(name, value)| {
+            !name.is_empty()
+                && !name.starts_with(' ')
+                && (value.is_empty() || value.starts_with(' '))
+        })
+}
+
+fn strip_list_marker(line: &str) -> &str {
+    if let Some(rest) = line
+        .strip_prefix(['-', '*', '+'])
+        .and_then(|rest| rest.strip_prefix(' '))
+    {
+        return rest.trim_start();
+    }
+
+    match line.split_once(". ") {
+        Some((enumerator, rest))
+            if enumerator == "#" || enumerator.chars().all(|c| c.is_ascii_digit()) =>
+        {
+            rest.trim_start()
+        }
+        _ => line,
+    }
+}
+
+/**
+ * Reduces inline markup to its text: roles like `` :ref:`text <target>` ``
+ * and references like `` `text <url>`_ `` become their text, and markers
+ * like `*emphasis*`, `**strong**`, ``` ``literal`` ```, and the underscore
+ * after a `reference_` are removed.
+ */
+fn strip_inline_markup(text: &str) -> String {
+    replace_interpreted_text(text)
+        .split(' ')
+        .map(|word| {
+            let word = word.replace('*', "");
+            let word = word.trim_matches('|');
+            match word.strip_suffix('_') {
+                Some(reference) if !reference.starts_with('_') => reference.to_string(),
+                _ => word.to_string(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+        .trim()
+        .to_string()
+}
+
+fn replace_interpreted_text(text: &str) -> String {
+    let mut output = String::new();
+    let mut rest = text;
+
+    while let Some(start) = rest.find('`') {
+        let (before, after) = rest.split_at(start);
+        output.push_str(strip_trailing_role(before));
+
+        let (delimiter, inner) = match after.strip_prefix("``") {
+            Some(inner) => ("``", inner),
+            None => ("`", &after[1..]),
+        };
+
+        let Some(end) = inner.find(delimiter) else {
+            output.push_str(after);
+            rest = "";
+            break;
+        };
+
+        let content = &inner[..end];
+        if delimiter == "``" {
+            output.push_str(content);
+        } else {
+            output.push_str(reference_text(content));
+        }
+        rest = inner[end + delimiter.len()..].trim_start_matches('_');
+    }
+
+    output.push_str(rest);
+    output
+}
+
+/**
+ * Removes a role like `:ref:` from the end of the text before interpreted
+ * text.
+ */
+fn strip_trailing_role(text: &str) -> &str {
+    let Some(without_colon) = text.strip_suffix(':') else {
+        return text;
+    };
+    let Some(role_start) = without_colon.rfind(':') else {
+        return text;
+    };
+
+    let role = &without_colon[role_start + 1..];
+    let is_role = !role.is_empty()
+        && role
+            .chars()
+            .all(|c| c.is_alphanumeric() || "-_.+".contains(c));
+    if is_role {
+        &text[..role_start]
+    } else {
+        text
+    }
+}
+
+/**
+ * The text of a reference like `text <target>`, or the whole content if it
+ * doesn't have a separate target.
+ */
+fn reference_text(content: &str) -> &str {
+    match content.strip_suffix('>').and_then(|c| c.rsplit_once('<')) {
+        Some((text, _target)) if !text.trim().is_empty() => text.trim(),
+        Some((_, target)) => target,
+        None => content,
+    }
+}
+
+/**
+ * Generates a section ID the way docutils does: the title in lowercase and
+ * without accents, with each run of other characters turned into a hyphen,
+ * and without leading digits or hyphens. Repeated IDs become `id1`, `id2`,
+ * and so on.
+ */
+fn generate_id(title: &str, used_ids: &HashSet<String>) -> String {
+    let mut id = String::new();
+    for c in title.to_lowercase().nfkd() {
+        if c.is_ascii_alphanumeric() {
+            id.push(c);
+        } else if c.is_ascii() && !id.is_empty() && !id.ends_with('-') {
+            id.push('-');
+        }
+    }
+    let id = id
+        .trim_start_matches(|c: char| c == '-' || c.is_ascii_digit())
+        .trim_end_matches('-')
+        .to_string();
+
+    if !id.is_empty() && !used_ids.contains(&id) {
+        return id;
+    }
+
+    let mut number = 1;
+    while used_ids.contains(&format!("id{number}")) {
+        number += 1;
+    }
+    format!("id{number}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{File, Filetype, InputConfig, OutputConfig};
+    use crate::InternalWordAnnotation;
+    use pretty_assertions::assert_eq;
+
+    fn section(anchor: Option<&str>, text: &str) -> Section {
+        Section {
+            anchor: anchor.map(ToString::to_string),
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn sections_get_labels_or_generated_anchors() {
+        let rst = r"==========
+User Guide
+==========
+
+:author: Jane
+
+Welcome to the **guide**. See `the site <https://stork-search.net>`_.
+
+.. This is a comment
+   that spans lines.
+
+Getting Started
+---------------
+
+.. note::
+
+   Read this first, then run ``stork build``.
+
+.. _install-steps:
+
+Installing *Stork*
+------------------
+
+1. Download the binary with :command:`curl`.
+2. Read the Install_ notes. Example::
+
+      stork build
+
+.. toctree::
+   :maxdepth: 2
+
+   install
+   usage
+
+Getting Started
+---------------
+";
+
+        assert_eq!(
+            parse_sections(rst),
+            vec![
+                section(None, ""),
+                section(
+                    Some("user-guide"),
+                    "User Guide\nWelcome to the guide. See the site.\n"
+                ),
+                section(
+                    Some("getting-started"),
+                    "Getting Started\nRead this first, then run stork build.\n"
+                ),
+                section(
+                    Some("install-steps"),
+                    "Installing Stork\nDownload the binary with curl.\nRead the Install notes. Example:\nstork build\n"
+                ),
+                section(Some("id1"), "Getting Started\n"),
+            ]
+        );
+    }
+
+    #[test]
+    fn words_link_to_their_section_when_saving_nearest_ids() {
+        let read_result = ReadResult {
+            buffer: "Intro\n\nUsage\n=====\n\nRun it".to_string(),
+            filetype: Some(Filetype::ReStructuredText),
+            frontmatter_fields: None,
+            frontmatter_title: None,
+        };
+        let words = |save_nearest_html_id| {
+            let config = ReaderConfig {
+                global: InputConfig::default(),
+                file: File::default(),
+                output: OutputConfig {
+                    save_nearest_html_id,
+                    ..OutputConfig::default()
+                },
+            };
+            generate(&config, &read_result)
+                .unwrap()
+                .word_list
+                .into_iter()
+                .map(|word| (word.word, word.internal_annotations))
+                .collect::<Vec<(String, Vec<InternalWordAnnotation>)>>()
+        };
+
+        let usage = || vec![InternalWordAnnotation::UrlSuffix("#usage".to_string())];
+        assert_eq!(
+            words(true),
+            vec![
+                ("Intro".to_string(), vec![]),
+                ("Usage".to_string(), usage()),
+                ("Run".to_string(), usage()),
+                ("it".to_string(), usage()),
+            ]
+        );
+        assert!(words(false)
+            .iter()
+            .all(|(_, annotations)| annotations.is_empty()));
+    }
+
+    #[test]
+    fn ids_are_generated_like_docutils() {
+        let used_ids = HashSet::from(["faq".to_string()]);
+        assert_eq!(
+            generate_id("What's new in v1.2?", &used_ids),
+            "what-s-new-in-v1-2"
+        );
+        assert_eq!(generate_id("1. Über café", &used_ids), "uber-cafe");
+        assert_eq!(generate_id("FAQ", &used_ids), "id1");
+    }
+}
@@ -18,13 +18,14 @@ pub fn generate(
     let mut word_list: Vec<AnnotatedWord> = Vec::new();
 
     for sub in subs {
-        let mut annotated_words_for_this_sub =
-            sub.text
-                .make_annotated_words_with_annotations(|_word, internal_annotations| {
-                    internal_annotations.push(InternalWordAnnotation::UrlSuffix(
-                        build_srt_url_time_suffix(&sub.start_time, &config.global.srt_config),
-                    ));
-                });
+        let mut annotated_words_for_this_sub = sub.text.make_annotated_words_with_annotations(
+            config.get_tokenizer(),
+            |_word, internal_annotations| {
+                internal_annotations.push(InternalWordAnnotation::UrlSuffix(
+                    build_srt_url_time_suffix(&sub.start_time, &config.global.srt_config),
+                ));
+            },
+        );
 
         word_list.append(&mut annotated_words_for_this_sub);
     }
@@ -33,15 +34,32 @@ pub fn generate(
 }
 
 fn build_srt_url_time_suffix(time: &srtparse::Time, srt_config: &SRTConfig) -> String {
+    build_url_time_suffix(time.hours, time.minutes, time.seconds, srt_config)
+}
+
+/**
+ * Builds the suffix that links a subtitle's URL to the time a cue starts.
+ * Also used for `.vtt` subtitles, which share the `SRTConfig`.
+ */
+pub(super) fn build_url_time_suffix(
+    hours: u64,
+    minutes: u64,
+    seconds: u64,
+    srt_config: &SRTConfig,
+) -> String {
+    // Saturating, so that a malformed cue with an enormous hour can't
+    // overflow
+    let total_minutes = hours.saturating_mul(60).saturating_add(minutes);
     let time_string = match srt_config.timestamp_format {
-        SRTTimestampFormat::NumberOfSeconds => {
-            ((time.hours) * 3600 + (time.minutes) * 60 + (time.seconds)).to_string()
-        }
+        SRTTimestampFormat::NumberOfSeconds => total_minutes
+            .saturating_mul(60)
+            .saturating_add(seconds)
+            .to_string(),
         SRTTimestampFormat::MinutesAndSeconds => {
-            if time.minutes > 0 {
-                format!("{}m{}s", time.hours * 60 + time.minutes, time.seconds)
+            if total_minutes > 0 {
+                format!("{total_minutes}m{seconds}s")
             } else {
-                format!("{}s", time.seconds)
+                format!("{seconds}s")
             }
         }
     };
@@ -57,7 +75,9 @@ mod tests {
 
     use crate::{
         config::SRTConfig,
-        index_v3::build::fill_intermediate_entries::word_list_generators::srt_word_list_generator::build_srt_url_time_suffix,
+        index_v3::build::fill_intermediate_entries::word_list_generators::srt_word_list_generator::{
+            build_srt_url_time_suffix, build_url_time_suffix,
+        },
     };
 
     #[test]
@@ -116,4 +136,30 @@ mod tests {
         let expected = "76m12s";
         assert_eq!(computed, expected);
     }
+
+    #[test]
+    fn it_formats_time_suffix_correctly_minutes_and_seconds_on_the_hour() {
+        let config = SRTConfig {
+            timestamp_linking: true,
+            timestamp_template_string: "{}".to_string(),
+            timestamp_format: crate::config::SRTTimestampFormat::MinutesAndSeconds,
+        };
+
+        assert_eq!(build_url_time_suffix(1, 0, 5, &config), "60m5s");
+        assert_eq!(build_url_time_suffix(0, 0, 5, &config), "5s");
+    }
+
+    #[test]
+    fn it_does_not_overflow_on_enormous_times() {
+        let config = SRTConfig {
+            timestamp_linking: true,
+            timestamp_template_string: "{}".to_string(),
+            timestamp_format: crate::config::SRTTimestampFormat::NumberOfSeconds,
+        };
+
+        assert_eq!(
+            build_url_time_suffix(u64::MAX, 0, 0, &config),
+            u64::MAX.to_string()
+        );
+    }
 }
@@ -0,0 +1,251 @@
+use crate::index_v3::build::annotated_words_from_string::AnnotatedWordable;
+use crate::index_v3::build::fill_intermediate_entries::ReaderConfig;
+use crate::index_v3::AnnotatedWord;
+use crate::index_v3::AnnotatedWordList;
+use crate::InternalWordAnnotation;
+
+use super::srt_word_list_generator::build_url_time_suffix;
+use super::ReadResult;
+use super::WordListGenerationError;
+
+pub fn generate(
+    config: &ReaderConfig,
+    read_result: &ReadResult,
+) -> Result<AnnotatedWordList, WordListGenerationError> {
+    let cues = parse_cues(&read_result.buffer).ok_or(WordListGenerationError::InvalidWebVTT)?;
+    let mut word_list: Vec<AnnotatedWord> = Vec::new();
+
+    for cue in cues {
+        let mut annotated_words_for_this_cue = cue.text.make_annotated_words_with_annotations(
+            config.get_tokenizer(),
+            |_word, internal_annotations| {
+                internal_annotations.push(InternalWordAnnotation::UrlSuffix(
+                    build_url_time_suffix(
+                        cue.start_time.hours,
+                        cue.start_time.minutes,
+                        cue.start_time.seconds,
+                        &config.global.srt_config,
+                    ),
+                ));
+            },
+        );
+
+        word_list.append(&mut annotated_words_for_this_cue);
+    }
+
+    Ok(AnnotatedWordList { word_list })
+}
+
+#[derive(Debug, PartialEq)]
+struct Time {
+    hours: u64,
+    minutes: u64,
+    seconds: u64,
+}
+
+#[derive(Debug, PartialEq)]
+struct Cue {
+    start_time: Time,
+
+    /// The cue's text, with its tags removed
+    text: String,
+}
+
+/**
+ * Parses the cues out of a `.vtt` file. Returns `None` if the file doesn't
+ * start with the `WEBVTT` signature, or if a cue's timings can't be read.
+ *
+ * Cue identifiers and cue settings (like `align:start`) are read past, and
+ * `NOTE`, `STYLE`, and `REGION` blocks are skipped.
+ */
+fn parse_cues(buffer: &str) -> Option<Vec<Cue>> {
+    let buffer = buffer.trim_start_matches('\u{feff}').replace("\r\n", "\n");
+    let mut blocks = buffer.split("\n\n");
+
+    let header = blocks.next()?;
+    let signature = header.lines().next()?;
+    let after_signature = signature.strip_prefix("WEBVTT")?;
+    if !(after_signature.is_empty() || after_signature.starts_with([' ', '\t'])) {
+        return None;
+    }
+
+    let mut cues = vec![];
+    for block in blocks.map(|block| block.trim_matches('\n')) {
+        let first_line = block.lines().next().unwrap_or_default();
+        let is_skipped_block = ["NOTE", "STYLE", "REGION"].iter().any(|keyword| {
+            first_line == *keyword
+                || first_line.starts_with(&format!("{keyword} "))
+                || first_line.starts_with(&format!("{keyword}\t"))
+        });
+        if block.is_empty() || is_skipped_block {
+            continue;
+        }
+
+        let mut lines = block.lines().skip_while(|line| !line.contains("-->"));
+        let timings = lines.next()?;
+        let (start, _end_and_settings) = timings.split_once("-->")?;
+
+        cues.push(Cue {
+            start_time: parse_timestamp(start.trim())?,
+            text: strip_tags(&lines.collect::<Vec<&str>>().join("\n")),
+        });
+    }
+
+    Some(cues)
+}
+
+/**
+ * Parses a timestamp like `01:02:03.456` or `02:03.456`. Minutes and seconds
+ * have to be less than 60.
+ */
+fn parse_timestamp(timestamp: &str) -> Option<Time> {
+    let (time, _milliseconds) = timestamp.split_once('.')?;
+    let parts = time
+        .split(':')
+        .map(|part| part.parse::<u64>().ok())
+        .collect::<Option<Vec<u64>>>()?;
+
+    let time = match parts.as_slice() {
+        [hours, minutes, seconds] => Time {
+            hours: *hours,
+            minutes: *minutes,
+            seconds: *seconds,
+        },
+        [minutes, seconds] => Time {
+            hours: 0,
+            minutes: *minutes,
+            seconds: *seconds,
+        },
+        _ => return None,
+    };
+
+    (time.minutes < 60 && time.seconds < 60).then_some(time)
+}
+
+/**
+ * Removes the tags from a cue's text, including voice tags like
+ * `<v Roger Bingham>` along with the speaker's name, class tags like
+ * `<c.loud>`, and timestamp tags. Character references for the characters
+ * that the format requires to be escaped are decoded.
+ */
+fn strip_tags(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut is_in_tag = false;
+
+    for c in text.chars() {
+        match c {
+            '<' => is_in_tag = true,
+            '>' if is_in_tag => is_in_tag = false,
+            c if !is_in_tag => output.push(c),
+            _ => {}
+        }
+    }
+
+    output
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&nbsp;", " ")
+        .replace("&lrm;", "")
+        .replace("&rlm;", "")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{File, Filetype, InputConfig, OutputConfig, SRTConfig, SRTTimestampFormat};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn cues_are_parsed_with_their_start_times() {
+        let vtt = "\u{feff}WEBVTT - Interview\r\nKind: captions\r\n\r\nNOTE This is a comment\r\nthat spans lines\r\n\r\nSTYLE\r\n::cue { color: yellow }\r\n\r\nintro\r\n00:01.000 --> 00:04.000 align:start line:10%\r\n<v Roger Bingham>We are in New York City\r\n\r\n01:02:03.500 --> 01:02:05.000\r\n<c.loud>Fish &amp; chips</c> <00:02.500><i>please</i>\r\n";
+
+        assert_eq!(
+            parse_cues(vtt),
+            Some(vec![
+                Cue {
+                    start_time: Time {
+                        hours: 0,
+                        minutes: 0,
+                        seconds: 1
+                    },
+                    text: "We are in New York City".to_string()
+                },
+                Cue {
+                    start_time: Time {
+                        hours: 1,
+                        minutes: 2,
+                        seconds: 3
+                    },
+                    text: "Fish & chips please".to_string()
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn words_link_to_their_cue_start_time() {
+        let config = ReaderConfig {
+            global: InputConfig {
+                srt_config: SRTConfig {
+                    timestamp_template_string: "#t={}".to_string(),
+                    timestamp_format: SRTTimestampFormat::MinutesAndSeconds,
+                    ..SRTConfig::default()
+                },
+                ..InputConfig::default()
+            },
+            file: File::default(),
+            output: OutputConfig::default(),
+        };
+        let read_result = ReadResult {
+            buffer: "WEBVTT\n\n00:05.000 --> 00:06.000\nHello\n\n01:30.000 --> 01:32.000\n<v Narrator>World".to_string(),
+            filetype: Some(Filetype::WebVTT),
+            frontmatter_fields: None,
+            frontmatter_title: None,
+        };
+
+        let words = generate(&config, &read_result).unwrap().word_list;
+        assert_eq!(
+            words
+                .iter()
+                .map(|word| (word.word.as_str(), word.internal_annotations.clone()))
+                .collect::<Vec<(&str, Vec<InternalWordAnnotation>)>>(),
+            vec![
+                (
+                    "Hello",
+                    vec![InternalWordAnnotation::UrlSuffix("#t=5s".to_string())]
+                ),
+                (
+                    "World",
+                    vec![InternalWordAnnotation::UrlSuffix("#t=1m30s".to_string())]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn files_without_the_signature_or_with_bad_timings_are_invalid() {
+        assert_eq!(
+            parse_cues("1\n00:00:01,000 --> 00:00:02,000\nAn SRT file"),
+            None
+        );
+        assert_eq!(parse_cues("WEBVTTX\n\n00:01.000 --> 00:02.000\nText"), None);
+        assert_eq!(parse_cues("WEBVTT\n\n1:2 --> 00:02.000\nText"), None);
+        assert_eq!(parse_cues("WEBVTT\n"), Some(vec![]));
+    }
+
+    #[test]
+    fn timestamps_with_out_of_range_minutes_or_seconds_are_invalid() {
+        assert_eq!(parse_timestamp("00:60.000"), None);
+        assert_eq!(parse_timestamp("01:60:00.000"), None);
+        assert_eq!(parse_timestamp("99999999999999999999:00:00.000"), None);
+        assert_eq!(
+            parse_timestamp("18446744073709551615:59:59.999"),
+            Some(Time {
+                hours: u64::MAX,
+                minutes: 59,
+                seconds: 59
+            })
+        );
+    }
+}
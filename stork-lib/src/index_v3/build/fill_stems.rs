@@ -1,9 +1,11 @@
 use super::{remove_surrounding_punctuation, NormalizedEntry};
+use crate::{config::Normalization, index_v3::normalization::normalize};
 use rust_stemmers::Stemmer;
 use std::collections::BTreeMap;
 
 pub fn fill_stems(
     intermediate_entries: &[NormalizedEntry],
+    normalization: Normalization,
     stems: &mut BTreeMap<String, Vec<String>>,
 ) {
     for entry in intermediate_entries {
@@ -12,7 +14,7 @@ pub fn fill_stems(
         if let Some(stem_algorithm) = entry.stem_algorithm {
             for annotated_word in &contents.word_list {
                 let normalized_word =
-                    remove_surrounding_punctuation(&annotated_word.word.to_lowercase());
+                    remove_surrounding_punctuation(&normalize(&annotated_word.word, normalization));
                 let stem = Stemmer::create(stem_algorithm)
                     .stem(&normalized_word)
                     .to_string();
@@ -0,0 +1,246 @@
+use std::{collections::BTreeMap, fs, io, path::Path};
+
+use crate::{
+    config::Config,
+    index_v3::{normalization::normalize, Container},
+};
+
+use super::errors::IndexGenerationError;
+
+/**
+ * Adds an alias between every two words of a synonym group (a word and its
+ * synonyms), in both directions, so that searching for any of them finds the
+ * others. With `js = javascript, ecmascript`, searching for `ecmascript`
+ * finds pages about `javascript` too.
+ *
+ * Aliases are only added to words that are in the index, since an alias to
+ * a word that isn't would never return any results.
+ */
+pub fn fill_synonyms(
+    config: &Config,
+    containers: &mut BTreeMap<String, Container>,
+) -> Result<(), IndexGenerationError> {
+    let mut synonym_groups: Vec<(String, Vec<String>)> = config
+        .input
+        .synonyms
+        .iter()
+        .map(|(word, synonyms)| {
+            (
+                word.clone(),
+                synonyms
+                    .words()
+                    .into_iter()
+                    .map(ToString::to_string)
+                    .collect(),
+            )
+        })
+        .collect();
+
+    if let Some(synonyms_file) = &config.input.synonyms_file {
+        let path = Path::new(&config.input.base_directory).join(synonyms_file);
+        let contents = fs::read_to_string(&path).map_err(|error| match error.kind() {
+            io::ErrorKind::NotFound => IndexGenerationError::SynonymsFileNotFound(path.clone()),
+            kind => IndexGenerationError::SynonymsFileNotRead(path.clone(), kind),
+        })?;
+        synonym_groups.append(&mut parse_synonyms_file(&contents)?);
+    }
+
+    for (word, synonyms) in synonym_groups {
+        let group: Vec<String> = std::iter::once(word)
+            .chain(synonyms)
+            .map(|word| normalize(word.trim(), config.input.normalization))
+            .collect();
+
+        for word in &group {
+            for synonym in &group {
+                if synonym != word {
+                    add_alias(containers, word, synonym, config.input.synonym_score);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn add_alias(containers: &mut BTreeMap<String, Container>, from: &str, to: &str, score: u8) {
+    let target_has_results = containers
+        .get(to)
+        .is_some_and(|container| !container.results.is_empty());
+
+    if from.is_empty() || !target_has_results {
+        return;
+    }
+
+    let _alias_score = containers
+        .entry(from.to_string())
+        .or_default()
+        .aliases
+        .entry(to.to_string())
+        .and_modify(|existing_score| *existing_score = (*existing_score).max(score))
+        .or_insert(score);
+}
+
+fn parse_synonyms_file(contents: &str) -> Result<Vec<(String, Vec<String>)>, IndexGenerationError> {
+    let mut synonym_groups = vec![];
+
+    for (line_index, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let (word, synonyms) =
+            line.split_once('=')
+                .ok_or(IndexGenerationError::InvalidSynonymsFileLine(
+                    line_index + 1,
+                ))?;
+
+        synonym_groups.push((
+            word.to_string(),
+            synonyms.split(',').map(ToString::to_string).collect(),
+        ));
+    }
+
+    Ok(synonym_groups)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{InputConfig, Synonyms};
+    use crate::index_v3::SearchResult;
+    use pretty_assertions::assert_eq;
+
+    fn containers_with_words(words: &[&str]) -> BTreeMap<String, Container> {
+        words
+            .iter()
+            .map(|word| {
+                let mut container = Container::new();
+                let _result = container.results.insert(0, SearchResult::new());
+                (word.to_string(), container)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn synonyms_become_aliases_in_both_directions() {
+        let config = Config {
+            input: InputConfig {
+                synonyms: [("K8s".to_string(), Synonyms::Word("Kubernetes".to_string()))]
+                    .into_iter()
+                    .collect(),
+                synonym_score: 90,
+                ..InputConfig::default()
+            },
+            ..Config::default()
+        };
+
+        let mut containers = containers_with_words(&["k8s", "kubernetes"]);
+        fill_synonyms(&config, &mut containers).unwrap();
+
+        assert_eq!(containers["k8s"].aliases.get("kubernetes"), Some(&90));
+        assert_eq!(containers["kubernetes"].aliases.get("k8s"), Some(&90));
+    }
+
+    #[test]
+    fn synonyms_only_alias_words_in_the_index() {
+        let config = Config {
+            input: InputConfig {
+                synonyms: [(
+                    "js".to_string(),
+                    Synonyms::Words(vec!["javascript".to_string(), "ecmascript".to_string()]),
+                )]
+                .into_iter()
+                .collect(),
+                ..InputConfig::default()
+            },
+            ..Config::default()
+        };
+
+        let mut containers = containers_with_words(&["javascript"]);
+        fill_synonyms(&config, &mut containers).unwrap();
+
+        assert_eq!(containers["js"].aliases.get("javascript"), Some(&96));
+        assert_eq!(containers["js"].aliases.get("ecmascript"), None);
+        assert_eq!(containers["javascript"].aliases.get("ecmascript"), None);
+    }
+
+    #[test]
+    fn every_word_in_a_synonym_group_aliases_the_others() {
+        let config = Config {
+            input: InputConfig {
+                synonyms: [(
+                    "js".to_string(),
+                    Synonyms::Words(vec!["javascript".to_string(), "ecmascript".to_string()]),
+                )]
+                .into_iter()
+                .collect(),
+                ..InputConfig::default()
+            },
+            ..Config::default()
+        };
+
+        let mut containers = containers_with_words(&["js", "javascript", "ecmascript"]);
+        fill_synonyms(&config, &mut containers).unwrap();
+
+        for (word, synonyms) in [
+            ("js", ["javascript", "ecmascript"]),
+            ("javascript", ["js", "ecmascript"]),
+            ("ecmascript", ["js", "javascript"]),
+        ] {
+            let aliases: Vec<&str> = containers[word]
+                .aliases
+                .keys()
+                .map(String::as_str)
+                .collect();
+            let mut expected = synonyms.to_vec();
+            expected.sort_unstable();
+            assert_eq!(aliases, expected, "{word}");
+        }
+    }
+
+    #[test]
+    fn synonyms_file_errors_keep_the_kind_of_error() {
+        let config_with_synonyms_file = |synonyms_file: &str| Config {
+            input: InputConfig {
+                base_directory: "../test-assets".to_string(),
+                synonyms_file: Some(synonyms_file.to_string()),
+                ..InputConfig::default()
+            },
+            ..Config::default()
+        };
+
+        assert!(matches!(
+            fill_synonyms(&config_with_synonyms_file("missing.txt"), &mut BTreeMap::new()),
+            Err(IndexGenerationError::SynonymsFileNotFound(path))
+                if path == Path::new("../test-assets/missing.txt")
+        ));
+        assert!(matches!(
+            fill_synonyms(&config_with_synonyms_file("crawl"), &mut BTreeMap::new()),
+            Err(IndexGenerationError::SynonymsFileNotRead(path, _))
+                if path == Path::new("../test-assets/crawl")
+        ));
+    }
+
+    #[test]
+    fn synonyms_file_is_parsed() {
+        let contents = "# Abbreviations\nk8s = kubernetes\n\njs = javascript, ecmascript\n";
+        assert_eq!(
+            parse_synonyms_file(contents).unwrap(),
+            vec![
+                ("k8s ".to_string(), vec![" kubernetes".to_string()]),
+                (
+                    "js ".to_string(),
+                    vec![" javascript".to_string(), " ecmascript".to_string()]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn synonyms_file_line_without_equals_sign_fails() {
+        let error = parse_synonyms_file("k8s = kubernetes\njs javascript").unwrap_err();
+        assert_eq!(error, IndexGenerationError::InvalidSynonymsFileLine(2));
+    }
+}
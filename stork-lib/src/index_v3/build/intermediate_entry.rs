@@ -1,3 +1,4 @@
+use crate::config::Tokenizer;
 use crate::{
     index_v3::{AnnotatedWordList, Entry},
     Fields,
@@ -7,9 +8,11 @@ use rust_stemmers::Algorithm;
 pub struct NormalizedEntry {
     pub(super) annotated_word_list: AnnotatedWordList,
     pub(super) stem_algorithm: Option<Algorithm>,
+    pub(super) tokenizer: Tokenizer,
     pub(super) title: String,
     pub(super) url: String,
     pub(super) fields: Fields,
+    pub(super) boost: Option<f32>,
 }
 
 impl From<&NormalizedEntry> for Entry {
@@ -19,12 +22,15 @@ impl From<&NormalizedEntry> for Entry {
             title: ie.title.clone(),
             url: ie.url.clone(),
             fields: ie.fields.clone(),
+            boost: ie.boost,
+            tokenizer: ie.tokenizer,
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::config::Tokenizer;
     use crate::index_v3::AnnotatedWordList;
 
     use super::Entry;
@@ -43,19 +49,24 @@ mod tests {
             title: "My Title".to_string(),
             url: "https://example.com".to_string(),
             fields: fields.clone(),
+            boost: None,
+            tokenizer: Tokenizer::Standard,
         };
 
         let generated = Entry::from(&NormalizedEntry {
             annotated_word_list: AnnotatedWordList { word_list: vec![] },
             stem_algorithm: None,
+            tokenizer: Tokenizer::Standard,
             title: "My Title".to_string(),
             url: "https://example.com".to_string(),
             fields: fields.clone(),
+            boost: None,
         });
 
         assert_eq!(generated.contents, intended.contents);
         assert_eq!(generated.title, intended.title);
         assert_eq!(generated.url, intended.url);
         assert_eq!(generated.fields, intended.fields);
+        assert_eq!(generated.tokenizer, intended.tokenizer);
     }
 }
@@ -3,27 +3,37 @@
 use std::collections::BTreeMap;
 
 mod fill_containers;
+mod fill_facets;
 mod fill_intermediate_entries;
 mod fill_stems;
+mod fill_synonyms;
+mod stopword_list;
 
 mod annotated_words_from_string;
 pub mod errors;
 pub mod intermediate_entry;
 
 use fill_containers::fill_containers;
+use fill_facets::fill_facets;
 use fill_intermediate_entries::fill_intermediate_entries;
 use fill_stems::fill_stems;
+use fill_synonyms::fill_synonyms;
+use stopword_list::{stopword_list, stopwords_config};
 
 use errors::{DocumentError, IndexGenerationError, WordListGenerationError};
 
+use annotated_words_from_string::AnnotatedWordable;
 use intermediate_entry::NormalizedEntry;
 
 pub mod nudger;
 use nudger::Nudger;
 
-use crate::{config::Config, V3Index as Index};
+use crate::{
+    config::{Config, Ranking, StemmingConfig, StopwordsConfig, Tokenizer},
+    V3Index as Index,
+};
 
-use super::{Container, Entry, PassthroughConfig};
+use super::{Container, Entry, FacetValues, PassthroughConfig};
 
 #[derive(Debug)]
 pub struct BuildResult {
@@ -46,10 +56,38 @@ pub fn build(config: &Config) -> Result<BuildResult, IndexGenerationError> {
     }
 
     let mut stems: BTreeMap<String, Vec<String>> = BTreeMap::new();
-    fill_stems(&intermediate_entries, &mut stems);
+    fill_stems(
+        &intermediate_entries,
+        config.input.normalization,
+        &mut stems,
+    );
+
+    let stopwords = stopword_list(&config.input);
 
     let mut containers: BTreeMap<String, Container> = BTreeMap::new();
-    fill_containers(config, &intermediate_entries, &stems, &mut containers);
+    fill_containers(
+        config,
+        &intermediate_entries,
+        &stems,
+        &stopwords,
+        &mut containers,
+    );
+    fill_synonyms(config, &mut containers)?;
+
+    let mut facets: BTreeMap<String, FacetValues> = BTreeMap::new();
+    fill_facets(config, &intermediate_entries, &mut facets);
+
+    let entry_word_counts: Vec<usize> = if config.output.ranking == Ranking::BM25 {
+        intermediate_entries
+            .iter()
+            .map(|entry| {
+                entry.title.make_annotated_words(entry.tokenizer).len()
+                    + entry.annotated_word_list.word_list.len()
+            })
+            .collect()
+    } else {
+        vec![]
+    };
 
     let entries: Vec<Entry> = intermediate_entries
         .iter()
@@ -69,12 +107,43 @@ pub fn build(config: &Config) -> Result<BuildResult, IndexGenerationError> {
         excerpt_buffer: config.output.excerpt_buffer,
         excerpts_per_result: config.output.excerpts_per_result,
         displayed_results_count: config.output.displayed_results_count,
+        ranking: config.output.ranking,
+        stem_algorithm: match config.input.stemming {
+            StemmingConfig::Language(algorithm) => Some(algorithm),
+            StemmingConfig::None => None,
+        },
+        normalization: config.input.normalization,
+        stopwords: match stopwords_config(&config.input) {
+            StopwordsConfig::Language(rust_stemmers::Algorithm::English) => None,
+            _ => Some(stopwords),
+        },
+        remove_stopwords: config.input.remove_stopwords,
+        field_types: config
+            .output
+            .field_types
+            .iter()
+            .map(|(key, field_type)| (key.to_lowercase(), *field_type))
+            .collect(),
+        minimum_indexed_substring_length: config.input.minimum_indexed_substring_length,
+        minimum_index_ideographic_substring_length: config
+            .input
+            .minimum_index_ideographic_substring_length,
+        field_keys: entries
+            .iter()
+            .flat_map(|entry| entry.fields.keys())
+            .map(|key| key.to_lowercase())
+            .collect(),
+        uses_cjk_bigrams: entries
+            .iter()
+            .any(|entry| entry.tokenizer == Tokenizer::CJKBigram),
     };
 
     let index = Index {
         entries,
         containers,
         config: passthrough_config,
+        facets,
+        entry_word_counts,
     };
 
     Ok(BuildResult {
@@ -0,0 +1,141 @@
+use rust_stemmers::Algorithm;
+
+use crate::{
+    config::{InputConfig, StemmingConfig, StopwordsConfig},
+    index_v3::normalization::normalize,
+    stopwords,
+};
+
+/**
+ * Returns the configured stopwords, normalized the same way the index's
+ * words are.
+ *
+ * English uses Stork's own list, which is the one searches fall back to for
+ * indexes that don't store a list. Other languages use the NLTK lists; Tamil
+ * doesn't have one, so it has no stopwords.
+ */
+pub(super) fn stopword_list(config: &InputConfig) -> Vec<String> {
+    let words: Vec<String> = match stopwords_config(config) {
+        StopwordsConfig::None => vec![],
+        StopwordsConfig::Custom(words) => words,
+        StopwordsConfig::Language(Algorithm::English) => {
+            stopwords.iter().map(ToString::to_string).collect()
+        }
+        StopwordsConfig::Language(algorithm) => nltk_language_code(algorithm)
+            .map(|code| {
+                stop_words::get(code)
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect()
+            })
+            .unwrap_or_default(),
+    };
+
+    let mut normalized_words: Vec<String> = words
+        .iter()
+        .map(|word| normalize(word.trim(), config.normalization))
+        .filter(|word| !word.is_empty())
+        .collect();
+    normalized_words.sort();
+    normalized_words.dedup();
+    normalized_words
+}
+
+/**
+ * The configured stopwords. Without any, the stemming language's stopwords
+ * are used, so that changing the language of an index changes both.
+ */
+pub(super) fn stopwords_config(config: &InputConfig) -> StopwordsConfig {
+    config
+        .stopwords
+        .clone()
+        .unwrap_or_else(|| match config.stemming {
+            StemmingConfig::Language(algorithm) => StopwordsConfig::Language(algorithm),
+            StemmingConfig::None => StopwordsConfig::default(),
+        })
+}
+
+fn nltk_language_code(algorithm: Algorithm) -> Option<&'static str> {
+    match algorithm {
+        Algorithm::Arabic => Some("ar"),
+        Algorithm::Danish => Some("da"),
+        Algorithm::Dutch => Some("nl"),
+        Algorithm::English => Some("en"),
+        Algorithm::Finnish => Some("fi"),
+        Algorithm::French => Some("fr"),
+        Algorithm::German => Some("de"),
+        Algorithm::Greek => Some("el"),
+        Algorithm::Hungarian => Some("hu"),
+        Algorithm::Italian => Some("it"),
+        Algorithm::Norwegian => Some("no"),
+        Algorithm::Portuguese => Some("pt"),
+        Algorithm::Romanian => Some("ro"),
+        Algorithm::Russian => Some("ru"),
+        Algorithm::Spanish => Some("es"),
+        Algorithm::Swedish => Some("sv"),
+        Algorithm::Turkish => Some("tr"),
+        Algorithm::Tamil => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn english_uses_the_built_in_list() {
+        let list = stopword_list(&InputConfig::default());
+        assert_eq!(list.len(), {
+            let mut built_in = stopwords.to_vec();
+            built_in.sort_unstable();
+            built_in.dedup();
+            built_in.len()
+        });
+        assert!(list.contains(&"the".to_string()));
+    }
+
+    #[test]
+    fn other_languages_use_their_own_list() {
+        let list = stopword_list(&InputConfig {
+            stopwords: Some(StopwordsConfig::Language(Algorithm::French)),
+            ..InputConfig::default()
+        });
+        assert!(list.contains(&"les".to_string()));
+        assert!(!list.contains(&"the".to_string()));
+    }
+
+    #[test]
+    fn stopwords_default_to_the_stemming_language() {
+        let list = stopword_list(&InputConfig {
+            stemming: StemmingConfig::Language(Algorithm::French),
+            ..InputConfig::default()
+        });
+        assert!(list.contains(&"les".to_string()));
+
+        let list = stopword_list(&InputConfig {
+            stemming: StemmingConfig::Language(Algorithm::French),
+            stopwords: Some(StopwordsConfig::Language(Algorithm::English)),
+            ..InputConfig::default()
+        });
+        assert!(list.contains(&"the".to_string()));
+
+        let list = stopword_list(&InputConfig {
+            stemming: StemmingConfig::None,
+            ..InputConfig::default()
+        });
+        assert!(list.contains(&"the".to_string()));
+    }
+
+    #[test]
+    fn custom_lists_are_normalized() {
+        let list = stopword_list(&InputConfig {
+            stopwords: Some(StopwordsConfig::Custom(vec![
+                "Stork".to_string(),
+                " stork ".to_string(),
+                String::new(),
+            ])),
+            ..InputConfig::default()
+        });
+        assert_eq!(list, vec!["stork".to_string()]);
+    }
+}
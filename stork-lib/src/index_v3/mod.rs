@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 use smart_default::SmartDefault;
 
@@ -7,9 +7,11 @@ type EntryIndex = usize;
 type AliasTarget = String;
 type Score = u8;
 
+mod normalization;
 mod read;
 mod scores;
 mod search;
+mod tokenizer;
 
 #[cfg(feature = "build-v3")]
 mod build;
@@ -26,16 +28,29 @@ pub use {
 
 pub use search::search;
 
-use crate::config::{OutputConfig, TitleBoost};
+use crate::config::{
+    FieldType, InputConfig, Normalization, OutputConfig, Ranking, TitleBoost, Tokenizer,
+};
 use crate::{Fields, InternalWordAnnotation};
 
 mod write;
 
+#[cfg(test)]
+mod test_helpers;
+
 #[derive(Serialize, Deserialize, Clone, Debug, Default)]
 pub struct Index {
     config: PassthroughConfig,
     entries: Vec<Entry>,
     containers: BTreeMap<String, Container>,
+
+    #[serde(default)]
+    facets: BTreeMap<String, FacetValues>,
+
+    /// The number of words in each entry's title and contents, indexed by
+    /// `EntryIndex`. Only filled when the index uses `Ranking::BM25`.
+    #[serde(default)]
+    entry_word_counts: Vec<usize>,
 }
 
 impl Index {
@@ -67,6 +82,60 @@ struct PassthroughConfig {
     excerpts_per_result: u8,
     #[default(OutputConfig::default().displayed_results_count)]
     displayed_results_count: u8,
+
+    #[serde(default)]
+    ranking: Ranking,
+
+    /// The index's global stemming algorithm, used to stem query words that
+    /// aren't in the index.
+    #[serde(default)]
+    stem_algorithm: Option<rust_stemmers::Algorithm>,
+
+    /// Indexes built before normalization was configurable only lowercased
+    /// their words, which is what `Normalization::None` does.
+    #[serde(default)]
+    normalization: Normalization,
+
+    /// The index's normalized stopwords. Indexes that don't store a list use
+    /// the built-in English list.
+    #[serde(default)]
+    stopwords: Option<Vec<String>>,
+
+    /// Whether stopwords were left out of the index, in which case phrases
+    /// skip over them.
+    #[serde(default)]
+    remove_stopwords: bool,
+
+    /// The declared types of fields, keyed by the lowercased field name.
+    #[serde(default)]
+    field_types: BTreeMap<String, FieldType>,
+
+    /// The shortest prefixes the index has aliases for, which are also the
+    /// shortest query words that can match the start of a title word.
+    #[default(InputConfig::default().minimum_indexed_substring_length)]
+    #[serde(default = "default_minimum_indexed_substring_length")]
+    minimum_indexed_substring_length: u8,
+    #[default(InputConfig::default().minimum_index_ideographic_substring_length)]
+    #[serde(default = "default_minimum_index_ideographic_substring_length")]
+    minimum_index_ideographic_substring_length: u8,
+
+    /// Every entry's field names, lowercased, which are the keys a query's
+    /// `key:value` filters can use.
+    #[serde(default)]
+    field_keys: BTreeSet<String>,
+
+    /// Whether any entry was split into CJK bigrams, in which case the
+    /// query's words are looked for as bigrams too.
+    #[serde(default)]
+    uses_cjk_bigrams: bool,
+}
+
+fn default_minimum_indexed_substring_length() -> u8 {
+    InputConfig::default().minimum_indexed_substring_length
+}
+
+fn default_minimum_index_ideographic_substring_length() -> u8 {
+    InputConfig::default().minimum_index_ideographic_substring_length
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Default)]
@@ -75,6 +144,15 @@ struct Entry {
     title: String,
     url: String,
     fields: Fields,
+
+    /// Multiplies the entry's search result score. `None` is the same as 1.
+    #[serde(default)]
+    boost: Option<f32>,
+
+    /// The tokenizer the entry's title and contents were split with, so that
+    /// they can be split into the same words when results are built.
+    #[serde(default)]
+    tokenizer: Tokenizer,
 }
 
 /**
@@ -102,10 +180,32 @@ impl Container {
     }
 }
 
+/**
+ * The values of a facetable field. Each distinct value is stored once, and
+ * each entry refers to its value by its position in `values`.
+ */
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+struct FacetValues {
+    values: Vec<String>,
+
+    /// Indexed by `EntryIndex`. `None` if the entry doesn't have the field.
+    entry_values: Vec<Option<usize>>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 struct SearchResult {
     excerpts: Vec<Excerpt>,
     score: Score,
+
+    /// How many times the word appears in the entry. Only filled when the
+    /// index uses `Ranking::BM25`.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    term_frequency: usize,
+}
+
+#[allow(clippy::trivially_copy_pass_by_ref)]
+fn is_zero(value: &usize) -> bool {
+    *value == 0
 }
 
 impl SearchResult {
@@ -114,6 +214,7 @@ impl SearchResult {
         SearchResult {
             excerpts: vec![],
             score: MATCHED_WORD_SCORE,
+            term_frequency: 0,
         }
     }
 }
@@ -132,7 +233,9 @@ struct Excerpt {
     fields: Fields,
 }
 
-#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, SmartDefault)]
+#[derive(
+    Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, SmartDefault,
+)]
 enum WordListSource {
     Title,
 
@@ -145,6 +248,11 @@ struct AnnotatedWord {
     word: String,
     internal_annotations: Vec<InternalWordAnnotation>,
     fields: Fields,
+
+    /// Whether the word came right after the previous word in the text, with
+    /// no whitespace between them, like the characters of a run of CJK text.
+    #[serde(default)]
+    joins_previous_word: bool,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -155,11 +263,7 @@ pub struct AnnotatedWordList {
 #[cfg(feature = "build-v3")]
 impl AnnotatedWordList {
     fn get_full_text(&self) -> String {
-        self.word_list
-            .iter()
-            .map(|aw| aw.word.clone())
-            .collect::<Vec<String>>()
-            .join(" ")
+        tokenizer::join_words(&self.word_list)
     }
 }
 
@@ -200,6 +304,7 @@ mod tests {
                     word: "is-a".to_string(),
                     internal_annotations: vec![InternalWordAnnotation::UrlSuffix("a".to_string())],
                     fields: HashMap::default(),
+                    joins_previous_word: false,
                 },
                 AnnotatedWord {
                     word: "set".to_string(),
@@ -220,6 +325,48 @@ mod tests {
         assert_eq!(intended, generated);
     }
 
+    #[test]
+    fn get_full_text_joins_words_without_whitespace_between_them() {
+        let word = |word: &str, joins_previous_word| AnnotatedWord {
+            word: word.to_string(),
+            joins_previous_word,
+            ..AnnotatedWord::default()
+        };
+        let generated = AnnotatedWordList {
+            word_list: vec![
+                word("Stork", false),
+                word("は", false),
+                word("東", true),
+                word("京", true),
+                word("で", true),
+            ],
+        }
+        .get_full_text();
+
+        assert_eq!(generated, "Stork は東京で");
+    }
+
+    #[test]
+    fn search_result_without_term_frequency_is_written_without_it() {
+        let result = SearchResult {
+            excerpts: vec![],
+            score: 128,
+            term_frequency: 0,
+        };
+        assert_eq!(
+            rmp_serde::to_vec(&result).unwrap(),
+            rmp_serde::to_vec(&(Vec::<Excerpt>::new(), 128_u8)).unwrap()
+        );
+
+        let bm25_result = SearchResult {
+            term_frequency: 5,
+            ..result
+        };
+        let bytes = rmp_serde::to_vec(&bm25_result).unwrap();
+        let read_result: SearchResult = rmp_serde::from_slice(&bytes).unwrap();
+        assert_eq!(read_result.term_frequency, 5);
+    }
+
     #[test]
     fn index_with_zero_excerpts_per_result_is_smaller() {
         let config = Config {
@@ -0,0 +1,110 @@
+use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
+
+use crate::config::Normalization;
+
+/**
+ * Normalizes a word the same way at build time and at search time, so that
+ * a query word matches the container key for the word it was indexed as.
+ */
+pub(super) fn normalize(word: &str, normalization: Normalization) -> String {
+    match normalization {
+        Normalization::None => word.to_lowercase(),
+        Normalization::Nfkc => word.nfkc().collect::<String>().to_lowercase(),
+        Normalization::FoldDiacritics => fold_diacritics(&word.nfkc().collect::<String>()),
+    }
+}
+
+fn fold_diacritics(word: &str) -> String {
+    let mut output = String::with_capacity(word.len());
+
+    for c in word.to_lowercase().nfd().filter(|c| !is_combining_mark(*c)) {
+        // These letters aren't built from a base letter and an accent, so
+        // decomposing them doesn't fold them.
+        match c {
+            'ß' => output.push_str("ss"),
+            'æ' => output.push_str("ae"),
+            'œ' => output.push_str("oe"),
+            'ø' => output.push('o'),
+            'ł' => output.push('l'),
+            'đ' => output.push('d'),
+            _ => output.push(c),
+        }
+    }
+
+    output.nfc().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index_v3::search::search;
+    use crate::index_v3::test_helpers::build_numbered_index;
+    use crate::{SearchOptions, V3Index as Index};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn no_normalization_only_lowercases() {
+        assert_eq!(normalize("Café", Normalization::None), "café");
+        assert_eq!(normalize("ＦＵＬＬ", Normalization::None), "ｆｕｌｌ");
+    }
+
+    #[test]
+    fn nfkc_normalizes_compatibility_characters() {
+        assert_eq!(normalize("ＦＵＬＬ", Normalization::Nfkc), "full");
+        assert_eq!(normalize("ﬁne", Normalization::Nfkc), "fine");
+        assert_eq!(normalize("Cafe\u{301}", Normalization::Nfkc), "café");
+    }
+
+    #[test]
+    fn diacritics_are_folded() {
+        assert_eq!(normalize("Café", Normalization::FoldDiacritics), "cafe");
+        assert_eq!(
+            normalize("Cafe\u{301}", Normalization::FoldDiacritics),
+            "cafe"
+        );
+        assert_eq!(
+            normalize("Straße", Normalization::FoldDiacritics),
+            "strasse"
+        );
+        assert_eq!(normalize("Über", Normalization::FoldDiacritics), "uber");
+        assert_eq!(normalize("Łódź", Normalization::FoldDiacritics), "lodz");
+        assert_eq!(normalize("日本語", Normalization::FoldDiacritics), "日本語");
+    }
+
+    fn build_normalization_test_index(contents: &str, normalization: Normalization) -> Index {
+        build_numbered_index(&[contents], |config| {
+            config.input.normalization = normalization;
+        })
+    }
+
+    #[test]
+    fn nfkc_normalization_matches_compatibility_characters() {
+        let index = build_normalization_test_index("Ｆｕｌｌ width text", Normalization::Nfkc);
+        let output = search(&index, "full", &SearchOptions::default());
+        assert_eq!(output.total_hit_count, 1);
+        assert_eq!(output.results[0].excerpts[0].text, "Ｆｕｌｌ width text");
+
+        let output = search(&index, "ＦＵＬＬ", &SearchOptions::default());
+        assert_eq!(output.total_hit_count, 1);
+    }
+
+    #[test]
+    fn diacritics_are_folded_when_configured() {
+        let contents = "Le café est ouvert dans la Straße.";
+
+        let index = build_normalization_test_index(contents, Normalization::Nfkc);
+        assert_eq!(
+            search(&index, "cafe", &SearchOptions::default()).total_hit_count,
+            0
+        );
+
+        let index = build_normalization_test_index(contents, Normalization::FoldDiacritics);
+        for query in ["cafe", "café", "CAFÉ", "strasse", "\"le cafe est\""] {
+            assert_eq!(
+                search(&index, query, &SearchOptions::default()).total_hit_count,
+                1,
+                "{query}"
+            );
+        }
+    }
+}
@@ -1,4 +1,5 @@
 pub const MATCHED_WORD_SCORE: u8 = 128;
 pub const PREFIX_SCORE: u8 = 127;
 pub const STEM_SCORE: u8 = 64;
+pub const FUZZY_SCORE: u8 = 48;
 pub const STOPWORD_SCORE: u8 = 16;
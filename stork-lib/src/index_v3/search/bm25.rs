@@ -0,0 +1,113 @@
+#![allow(clippy::cast_precision_loss)]
+#![allow(clippy::cast_possible_truncation)]
+#![allow(clippy::cast_sign_loss)]
+
+use std::collections::BTreeMap;
+
+use crate::index_v3::{scores::MATCHED_WORD_SCORE, EntryIndex, Score};
+use crate::V3Index as Index;
+
+use super::intermediate_excerpt::IntermediateExcerpt;
+
+/// Controls how quickly repeated occurrences of a word stop adding to the score.
+const K1: f64 = 1.2;
+
+/// Controls how much an entry's length dampens its score.
+const B: f64 = 0.75;
+
+/// BM25 scores are small floats; scale them up so they can be compared with
+/// (and summed alongside) the integer title boost.
+const SCALE: f64 = 100.0;
+
+/**
+ * Scores an entry against the words that matched it using BM25.
+ *
+ * Each word is weighted by the score of the best way it matched (an exact
+ * match counts fully, while prefix, stem, and stopword matches count for
+ * proportionally less), so BM25 keeps the distinctions the legacy scorer
+ * makes between kinds of matches. Prefix matches in indexes built without
+ * excerpts are recorded under the query word rather than the word they
+ * matched, so they don't have a term frequency and don't add to the score.
+ */
+pub(super) fn score(
+    index: &Index,
+    entry_index: EntryIndex,
+    intermediate_excerpts: &[IntermediateExcerpt],
+) -> usize {
+    let entry_count = index.entries.len() as f64;
+    let average_word_count = if index.entry_word_counts.is_empty() {
+        1.0
+    } else {
+        index.entry_word_counts.iter().sum::<usize>() as f64 / index.entry_word_counts.len() as f64
+    };
+    let word_count = index
+        .entry_word_counts
+        .get(entry_index)
+        .map_or(average_word_count, |count| *count as f64);
+
+    let mut match_scores: BTreeMap<&str, Score> = BTreeMap::new();
+    for ie in intermediate_excerpts {
+        let match_score = match_scores.entry(ie.query.as_str()).or_default();
+        *match_score = (*match_score).max(ie.score);
+    }
+
+    let total: f64 = match_scores
+        .into_iter()
+        .filter_map(|(word, match_score)| {
+            let container = index.containers.get(word)?;
+            let term_frequency = container.results.get(&entry_index)?.term_frequency.max(1) as f64;
+            let document_frequency = container.results.len() as f64;
+
+            let inverse_document_frequency =
+                (1.0 + (entry_count - document_frequency + 0.5) / (document_frequency + 0.5)).ln();
+
+            let term_frequency_saturation = (term_frequency * (K1 + 1.0))
+                / (term_frequency + K1 * (1.0 - B + B * word_count / average_word_count));
+
+            let match_weight = f64::from(match_score) / f64::from(MATCHED_WORD_SCORE);
+
+            Some(inverse_document_frequency * term_frequency_saturation * match_weight)
+        })
+        .sum();
+
+    (total * SCALE).round() as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::Ranking;
+    use crate::index_v3::search::search;
+    use crate::index_v3::test_helpers::build_numbered_index;
+    use crate::SearchOptions;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn bm25_ranking_favors_rare_words() {
+        let build_index = |ranking: Ranking| {
+            build_numbered_index(
+                &[
+                    "water water water water water flows downhill",
+                    "water and a quiet heron",
+                    "water everywhere",
+                ],
+                |config| config.output.ranking = ranking,
+            )
+        };
+
+        let first_title = |index| {
+            search(index, "water heron", &SearchOptions::default()).results[0]
+                .entry
+                .title
+                .clone()
+        };
+
+        let legacy_index = build_index(Ranking::Legacy);
+        assert!(legacy_index.entry_word_counts.is_empty());
+        assert_eq!(first_title(&legacy_index), "Document 0");
+
+        let bm25_index = build_index(Ranking::BM25);
+        assert_eq!(bm25_index.entry_word_counts, vec![9, 7, 4]);
+        assert_eq!(bm25_index.containers["water"].results[&0].term_frequency, 5);
+        assert_eq!(first_title(&bm25_index), "Document 1");
+    }
+}
@@ -3,21 +3,34 @@
 #![allow(clippy::cast_precision_loss)]
 #![allow(clippy::cast_sign_loss)]
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 
 use crate::{
-    config::TitleBoost,
-    index_v3::{Entry, PassthroughConfig, WordListSource},
+    index_v3::{
+        tokenizer::{join_words, split_into_annotated_words},
+        AnnotatedWord, Entry, PassthroughConfig, Score, WordListSource,
+    },
     Excerpt, HighlightRange, Result,
 };
 
 use super::intermediate_excerpt::IntermediateExcerpt;
+use super::title_match::get_title_match;
 
 #[derive(Debug, Clone, Default)]
 pub(super) struct EntryAndIntermediateExcerpts {
     pub(super) entry: Entry,
     pub(super) config: PassthroughConfig,
     pub(super) intermediate_excerpts: Vec<IntermediateExcerpt>,
+
+    /// The words in the query, used to score the entry's title.
+    pub(super) query_words: Vec<String>,
+
+    /// Whether the last query word can match the start of title words.
+    pub(super) last_query_word_is_prefix: bool,
+
+    /// Set when the index uses `Ranking::BM25`, in which case it replaces the
+    /// score derived from the entry's excerpts.
+    pub(super) bm25_score: Option<usize>,
 }
 
 #[allow(clippy::cast_possible_wrap, clippy::cast_possible_truncation)]
@@ -26,16 +39,19 @@ impl From<EntryAndIntermediateExcerpts> for Result {
         let entry = data.entry;
         let excerpt_buffer = data.config.excerpt_buffer as usize;
 
-        let split_contents: Vec<String> = entry
-            .contents
-            .split_whitespace()
-            .map(ToString::to_string)
-            .collect();
+        // Contents are split the same way they were when the index was built,
+        // so that word indices line up with the entry's excerpts.
+        let split_contents: Vec<AnnotatedWord> =
+            split_into_annotated_words(&entry.contents, entry.tokenizer);
 
         let mut ies: Vec<&IntermediateExcerpt> = data
             .intermediate_excerpts
             .iter()
-            .filter(|ie| ie.source == WordListSource::Contents)
+            .filter(|ie| {
+                // Indexes built without excerpts don't store entry contents,
+                // so there are no words to build an excerpt around.
+                ie.source == WordListSource::Contents && ie.word_index < split_contents.len()
+            })
             .collect();
 
         // Get rid of intermediate excerpts that refer to the same word index.
@@ -79,19 +95,17 @@ impl From<EntryAndIntermediateExcerpts> for Result {
                     split_contents.len(),
                 );
 
-                let text = split_contents[minimum_word_index..maximum_word_index].join(" ");
+                let text = join_words(&split_contents[minimum_word_index..maximum_word_index]);
 
                 let mut highlight_ranges: Vec<HighlightRange> = ies
                     .iter()
                     .map(|ie| {
-                        let beginning = split_contents[minimum_word_index..ie.word_index]
-                            .join(" ")
+                        let end = join_words(&split_contents[minimum_word_index..=ie.word_index])
                             .chars()
-                            .count()
-                            + 1;
+                            .count();
                         HighlightRange {
-                            beginning,
-                            end: beginning + split_contents[ie.word_index].clone().chars().count(),
+                            beginning: end - split_contents[ie.word_index].word.chars().count(),
+                            end,
                         }
                     })
                     .collect();
@@ -139,37 +153,45 @@ impl From<EntryAndIntermediateExcerpts> for Result {
         excerpts.sort_by_key(|e| -(e.score as i16));
         excerpts.truncate(data.config.excerpts_per_result as usize);
 
-        let split_title: Vec<&str> = entry
-            .title
-            .split(|c: char| c.is_ascii_whitespace() || c == '-')
-            .collect();
-        let mut title_highlight_ranges: Vec<HighlightRange> = data
+        let title_word_indices: BTreeSet<usize> = data
             .intermediate_excerpts
             .iter()
-            .filter(|&ie| ie.source == WordListSource::Title)
-            .map(|ie| {
-                let space_offset: usize = (ie.word_index != 0).into();
-                let beginning = split_title[0..ie.word_index].join(" ").len() + space_offset;
-                HighlightRange {
-                    beginning,
-                    end: beginning + split_title[ie.word_index].len(),
-                }
-            })
+            .filter(|ie| ie.source == WordListSource::Title)
+            .map(|ie| ie.word_index)
             .collect();
+        let title_match = get_title_match(
+            &entry.title,
+            &data.query_words,
+            data.last_query_word_is_prefix,
+            &title_word_indices,
+            &data.config.title_boost,
+            data.config.normalization,
+            entry.tokenizer,
+        );
 
-        title_highlight_ranges.sort();
-
-        let title_boost_modifier = title_highlight_ranges.len()
-            * match data.config.title_boost {
-                TitleBoost::Minimal => 25,
-                TitleBoost::Moderate => 75,
-                TitleBoost::Large => 150,
-                TitleBoost::Ridiculous => 5000,
-            };
+        let result_score = if let Some(bm25_score) = data.bm25_score {
+            bm25_score + title_match.score
+        } else if excerpts.is_empty() {
+            // Without excerpts, score by the best match for each word that
+            // matched the entry's contents.
+            let mut word_scores: BTreeMap<&str, Score> = BTreeMap::new();
+            for ie in data
+                .intermediate_excerpts
+                .iter()
+                .filter(|ie| ie.source == WordListSource::Contents)
+            {
+                let word_score = word_scores.entry(ie.query.as_str()).or_default();
+                *word_score = (*word_score).max(ie.score);
+            }
 
-        // Sort each result by a sum of an exponental backoff of its excerpts' scores.
-        // This more evenly weights a single high score excerpt vs multiple low score excerpts.
-        let result_score = {
+            word_scores
+                .values()
+                .map(|score| *score as usize)
+                .sum::<usize>()
+                + title_match.score
+        } else {
+            // Sort each result by a sum of an exponental backoff of its excerpts' scores.
+            // This more evenly weights a single high score excerpt vs multiple low score excerpts.
             let mut sorted_excerpt_scores: Vec<usize> = excerpts.iter().map(|e| e.score).collect();
             sorted_excerpt_scores.sort();
 
@@ -184,13 +206,18 @@ impl From<EntryAndIntermediateExcerpts> for Result {
                 })
                 .sum();
 
-            sum + title_boost_modifier
+            sum + title_match.score
+        };
+
+        let result_score = match entry.boost {
+            Some(boost) => (result_score as f32 * boost.max(0.0)).round() as usize,
+            None => result_score,
         };
 
         Result {
             entry: crate::Entry::from(entry),
             excerpts,
-            title_highlight_ranges,
+            title_highlight_ranges: title_match.highlight_ranges,
             score: result_score,
         }
     }
@@ -199,7 +226,11 @@ impl From<EntryAndIntermediateExcerpts> for Result {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::Tokenizer;
+    use crate::index_v3::search::search;
+    use crate::index_v3::test_helpers::{build_index, ranked_titles};
     use crate::index_v3::Entry;
+    use crate::SearchOptions;
     use pretty_assertions::assert_eq;
 
     #[test]
@@ -210,6 +241,8 @@ mod tests {
                 title: "The quick brown fox jumps over the lazy dog".to_string(),
                 url: String::default(),
                 fields: HashMap::default(),
+                boost: None,
+                tokenizer: Tokenizer::Standard,
             },
             config: PassthroughConfig::default(),
             intermediate_excerpts: vec![
@@ -232,6 +265,9 @@ mod tests {
                     fields: HashMap::default(),
                 },
             ],
+            query_words: vec![],
+            last_query_word_is_prefix: false,
+            bm25_score: None,
         };
 
         let single_excerpt = EntryAndIntermediateExcerpts {
@@ -240,6 +276,8 @@ mod tests {
                 title: "The quick brown fox jumps over the lazy dog".to_string(),
                 url: String::default(),
                 fields: HashMap::default(),
+                boost: None,
+                tokenizer: Tokenizer::Standard,
             },
             config: PassthroughConfig::default(),
             intermediate_excerpts: vec![IntermediateExcerpt {
@@ -251,6 +289,9 @@ mod tests {
                 internal_annotations: Vec::default(),
                 fields: HashMap::default(),
             }],
+            query_words: vec![],
+            last_query_word_is_prefix: false,
+            bm25_score: None,
         };
 
         let result_multiple_excerpts = Result::from(multiple_excerpts);
@@ -268,6 +309,8 @@ mod tests {
                 title: "The quick brown fox jumps over the lazy dog".to_string(),
                 url: String::default(),
                 fields: HashMap::default(),
+                boost: None,
+                tokenizer: Tokenizer::Standard,
             },
             config: PassthroughConfig::default(),
             intermediate_excerpts: vec![
@@ -290,6 +333,9 @@ mod tests {
                     fields: HashMap::default(),
                 },
             ],
+            query_words: vec![],
+            last_query_word_is_prefix: false,
+            bm25_score: None,
         };
         let output_result = Result::from(entry_and_intermediate_excerpts);
         let title_highlight_ranges = output_result.title_highlight_ranges;
@@ -310,6 +356,8 @@ mod tests {
                 title: "Introduction".to_string(),
                 url: String::default(),
                 fields: HashMap::default(),
+                boost: None,
+                tokenizer: Tokenizer::Standard,
             },
             config: PassthroughConfig::default(),
             intermediate_excerpts: vec![
@@ -332,6 +380,9 @@ mod tests {
                     fields: HashMap::default(),
                 },
             ],
+            query_words: vec![],
+            last_query_word_is_prefix: false,
+            bm25_score: None,
         };
 
         let output_result = Result::from(entry_and_intermediate_excerpts);
@@ -398,6 +449,8 @@ mod tests {
                 title: "api-methods-animate".to_string(),
                 url: String::default(),
                 fields: HashMap::default(),
+                boost: None,
+                tokenizer: Tokenizer::Standard,
             },
             config: PassthroughConfig::default(),
             intermediate_excerpts: vec![IntermediateExcerpt {
@@ -409,10 +462,36 @@ mod tests {
                 internal_annotations: Vec::default(),
                 fields: HashMap::default(),
             }],
+            query_words: vec![],
+            last_query_word_is_prefix: false,
+            bm25_score: None,
         };
 
         let output_result = Result::from(entry_and_intermediate_excerpts);
 
         println!("{output_result:?}");
     }
+
+    #[test]
+    fn boosted_files_outrank_files_with_the_same_words() {
+        let contents = "How to configure the search index";
+        let index = build_index(
+            &[
+                ("Changelog", contents),
+                ("Guide", contents),
+                ("Landing Page", contents),
+            ],
+            |config| {
+                config.input.files[0].boost = Some(0.5);
+                config.input.files[1].boost = Some(2.0);
+            },
+        );
+
+        let output = search(&index, "configure", &SearchOptions::default());
+        assert_eq!(
+            ranked_titles(&output),
+            vec!["Guide", "Landing Page", "Changelog"]
+        );
+        assert_eq!(output.results[0].score, 2 * output.results[1].score);
+    }
 }
@@ -0,0 +1,148 @@
+use std::collections::BTreeSet;
+
+use crate::{SearchOptions, V3Index as Index};
+
+use super::super::{scores::MATCHED_WORD_SCORE, EntryIndex};
+use super::intermediate_excerpt::IntermediateExcerpt;
+use super::phrase;
+use super::query::{Clause, Expression, Occurrence, QueryTerm};
+use super::{get_container_intermediate_excerpts, get_word_intermediate_excerpts};
+
+/**
+ * The entries that match some part of a query, along with the intermediate
+ * excerpts that will be used to score and highlight them.
+ */
+#[derive(Debug, Default)]
+struct Matches {
+    entries: BTreeSet<EntryIndex>,
+    intermediate_excerpts: Vec<IntermediateExcerpt>,
+}
+
+impl From<Vec<IntermediateExcerpt>> for Matches {
+    fn from(intermediate_excerpts: Vec<IntermediateExcerpt>) -> Self {
+        Matches {
+            entries: intermediate_excerpts
+                .iter()
+                .map(|ie| ie.entry_index)
+                .collect(),
+            intermediate_excerpts,
+        }
+    }
+}
+
+/**
+ * How a query word is matched against the index's words.
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum WordMatching {
+    /// The word also matches its prefix and stem aliases, and misspellings
+    /// of it if fuzzy matching is turned on.
+    Expanded,
+
+    /// The word only matches itself. Excluded clauses are matched this way,
+    /// so that `-api` doesn't also remove results that only mention `apiary`.
+    Exact,
+}
+
+pub(super) fn evaluate(
+    index: &Index,
+    clauses: &[Clause],
+    options: &SearchOptions,
+) -> Vec<IntermediateExcerpt> {
+    evaluate_clauses(index, clauses, options, WordMatching::Expanded).intermediate_excerpts
+}
+
+fn evaluate_clauses(
+    index: &Index,
+    clauses: &[Clause],
+    options: &SearchOptions,
+    word_matching: WordMatching,
+) -> Matches {
+    let mut required_entries: Option<BTreeSet<EntryIndex>> = None;
+    let mut optional_entries: BTreeSet<EntryIndex> = BTreeSet::new();
+    let mut excluded_entries: BTreeSet<EntryIndex> = BTreeSet::new();
+    let mut intermediate_excerpts: Vec<IntermediateExcerpt> = vec![];
+
+    for clause in clauses {
+        let clause_word_matching = match clause.occurrence {
+            Occurrence::Excluded => WordMatching::Exact,
+            _ => word_matching,
+        };
+        let mut matches =
+            evaluate_expression(index, &clause.expression, options, clause_word_matching);
+
+        match clause.occurrence {
+            Occurrence::Optional => {
+                optional_entries.append(&mut matches.entries);
+                intermediate_excerpts.append(&mut matches.intermediate_excerpts);
+            }
+
+            Occurrence::Required => {
+                required_entries = Some(match required_entries {
+                    Some(entries) => entries.intersection(&matches.entries).copied().collect(),
+                    None => matches.entries,
+                });
+                intermediate_excerpts.append(&mut matches.intermediate_excerpts);
+            }
+
+            Occurrence::Excluded => {
+                excluded_entries.append(&mut matches.entries);
+            }
+        }
+    }
+
+    let entries: BTreeSet<EntryIndex> = required_entries
+        .unwrap_or(optional_entries)
+        .difference(&excluded_entries)
+        .copied()
+        .collect();
+
+    intermediate_excerpts.retain(|ie| entries.contains(&ie.entry_index));
+
+    Matches {
+        entries,
+        intermediate_excerpts,
+    }
+}
+
+fn evaluate_expression(
+    index: &Index,
+    expression: &Expression,
+    options: &SearchOptions,
+    word_matching: WordMatching,
+) -> Matches {
+    match expression {
+        Expression::Term(QueryTerm::Word(word)) => match word_matching {
+            WordMatching::Expanded => {
+                Matches::from(get_word_intermediate_excerpts(index, word, options))
+            }
+            WordMatching::Exact => Matches::from(
+                index
+                    .containers
+                    .get(word)
+                    .map(|container| {
+                        get_container_intermediate_excerpts(word, container, MATCHED_WORD_SCORE)
+                    })
+                    .unwrap_or_default(),
+            ),
+        },
+
+        Expression::Term(QueryTerm::Phrase(words)) => {
+            Matches::from(phrase::get_intermediate_excerpts(index, words))
+        }
+
+        Expression::Or(expressions) => {
+            let mut output = Matches::default();
+            for expression in expressions {
+                let mut matches = evaluate_expression(index, expression, options, word_matching);
+                output.entries.append(&mut matches.entries);
+                output
+                    .intermediate_excerpts
+                    .append(&mut matches.intermediate_excerpts);
+            }
+            output
+        }
+
+        Expression::Group(clauses) => evaluate_clauses(index, clauses, options, word_matching),
+    }
+}
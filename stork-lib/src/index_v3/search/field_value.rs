@@ -0,0 +1,190 @@
+use std::cmp::Ordering;
+
+use crate::config::FieldType;
+
+/**
+ * A field's value, parsed according to the field's declared type so that it
+ * can be compared with other values of the same field.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub(super) enum FieldValue {
+    Keyword(String),
+    Number(f64),
+
+    /// Seconds since the Unix epoch, in UTC
+    Date(i64),
+}
+
+impl FieldValue {
+    pub(super) fn parse(value: &str, field_type: FieldType) -> Option<Self> {
+        let value = value.trim();
+        match field_type {
+            FieldType::Keyword => Some(FieldValue::Keyword(value.to_lowercase())),
+            FieldType::Number => value
+                .parse::<f64>()
+                .ok()
+                .filter(|number| !number.is_nan())
+                .map(FieldValue::Number),
+            FieldType::Date => parse_date(value).map(FieldValue::Date),
+        }
+    }
+
+    pub(super) fn compare(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (FieldValue::Keyword(a), FieldValue::Keyword(b)) => Some(a.cmp(b)),
+            (FieldValue::Number(a), FieldValue::Number(b)) => a.partial_cmp(b),
+            (FieldValue::Date(a), FieldValue::Date(b)) => Some(a.cmp(b)),
+            _ => None,
+        }
+    }
+}
+
+/**
+ * Parses an ISO 8601 date, optionally followed by a time and a UTC offset:
+ * `2024-01-01`, `2024-01-01T09:30`, `2024-01-01 09:30:00`, or
+ * `2024-01-01T09:30:00.000+02:00`. Times without an offset are treated as UTC.
+ */
+fn parse_date(value: &str) -> Option<i64> {
+    let (date, time) = match value.find(['T', 't', ' ']) {
+        Some(index) => (&value[..index], Some(&value[index + 1..])),
+        None => (value, None),
+    };
+
+    let mut date_parts = date.splitn(3, '-');
+    let year: i64 = parse_digits(date_parts.next()?, 4)?;
+    let month: i64 = parse_digits(date_parts.next()?, 2)?;
+    let day: i64 = parse_digits(date_parts.next()?, 2)?;
+
+    if !(1..=12).contains(&month) || day < 1 || day > days_in_month(year, month) {
+        return None;
+    }
+
+    let seconds = match time {
+        Some(time) => parse_time(time)?,
+        None => 0,
+    };
+
+    Some(days_from_civil(year, month, day) * 86400 + seconds)
+}
+
+/**
+ * Returns the seconds since midnight UTC for a time like `09:30`,
+ * `09:30:00Z`, or `09:30:00.5-05:00`.
+ */
+fn parse_time(time: &str) -> Option<i64> {
+    let (time, offset_seconds) = if let Some(time) = time.strip_suffix(['Z', 'z']) {
+        (time, 0)
+    } else if let Some(index) = time.rfind(['+', '-']) {
+        let sign = if time[index..].starts_with('-') {
+            -1
+        } else {
+            1
+        };
+        let (hours, minutes) = time[index + 1..].split_once(':')?;
+        let offset = parse_digits(hours, 2)? * 3600 + parse_digits(minutes, 2)? * 60;
+        (&time[..index], sign * offset)
+    } else {
+        (time, 0)
+    };
+
+    let mut time_parts = time.splitn(3, ':');
+    let hours = parse_digits(time_parts.next()?, 2)?;
+    let minutes = parse_digits(time_parts.next()?, 2)?;
+    let seconds = match time_parts.next() {
+        Some(seconds) => parse_digits(seconds.split('.').next()?, 2)?,
+        None => 0,
+    };
+
+    if hours > 23 || minutes > 59 || seconds > 60 {
+        return None;
+    }
+
+    Some(hours * 3600 + minutes * 60 + seconds - offset_seconds)
+}
+
+fn parse_digits(string: &str, length: usize) -> Option<i64> {
+    if string.len() != length || !string.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    string.parse().ok()
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/**
+ * The number of days between 1970-01-01 and the given date, using the
+ * algorithm from <http://howardhinnant.github.io/date_algorithms.html#days_from_civil>.
+ */
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = if year >= 0 { year } else { year - 399 } / 400;
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn dates_are_parsed_as_seconds_since_the_epoch() {
+        assert_eq!(parse_date("1970-01-01"), Some(0));
+        assert_eq!(parse_date("2000-03-01"), Some(951_868_800));
+        assert_eq!(parse_date("2024-02-29T12:00:00Z"), Some(1_709_208_000));
+        assert_eq!(parse_date("2024-02-29 12:00"), Some(1_709_208_000));
+        assert_eq!(
+            parse_date("2024-02-29T14:00:00.250+02:00"),
+            Some(1_709_208_000)
+        );
+        assert_eq!(parse_date("1969-12-31T23:00-01:00"), Some(0));
+    }
+
+    #[test]
+    fn invalid_dates_are_not_parsed() {
+        for date in [
+            "",
+            "2024",
+            "2024-1-01",
+            "2023-02-29",
+            "2024-13-01",
+            "2024-01-01T25:00",
+            "January 1st",
+        ] {
+            assert_eq!(parse_date(date), None, "{date}");
+        }
+    }
+
+    #[test]
+    fn values_compare_by_type() {
+        let parse = |value: &str, field_type| FieldValue::parse(value, field_type).unwrap();
+
+        assert_eq!(
+            parse("9", FieldType::Number).compare(&parse("10", FieldType::Number)),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            parse("9", FieldType::Keyword).compare(&parse("10", FieldType::Keyword)),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(
+            parse("Guide", FieldType::Keyword).compare(&parse("guide", FieldType::Keyword)),
+            Some(Ordering::Equal)
+        );
+        assert_eq!(
+            parse("2024-01-01", FieldType::Date).compare(&parse("2023-12-31", FieldType::Date)),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(FieldValue::parse("ten", FieldType::Number), None);
+    }
+}
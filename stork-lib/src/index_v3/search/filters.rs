@@ -0,0 +1,266 @@
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, BTreeSet},
+};
+
+use crate::{config::FieldType, Comparison, Fields, RangeFilter, SearchOptions};
+
+use super::{field_value::FieldValue, query::FieldFilter};
+
+/**
+ * Decides whether an entry should be returned based on its fields.
+ *
+ * Filters for the same key are combined, so `category:guide category:api`
+ * returns entries in either category, while filters for different keys all
+ * have to match. Filters from the query and from `SearchOptions` are merged
+ * the same way. Range filters all have to match.
+ */
+#[derive(Debug, Default)]
+pub(super) struct EntryFilter {
+    accepted_values: BTreeMap<String, BTreeSet<String>>,
+    rejected_values: BTreeMap<String, BTreeSet<String>>,
+    ranges: Vec<FieldRange>,
+}
+
+/**
+ * A range filter whose value has been parsed as its field's type. If the
+ * value couldn't be parsed, the filter doesn't match any entries.
+ */
+#[derive(Debug)]
+struct FieldRange {
+    key: String,
+    field_type: FieldType,
+    comparison: Comparison,
+    value: Option<FieldValue>,
+}
+
+impl FieldRange {
+    fn new(range_filter: &RangeFilter, field_types: &BTreeMap<String, FieldType>) -> Self {
+        let field_type = field_types
+            .get(&range_filter.key)
+            .copied()
+            .unwrap_or_default();
+
+        FieldRange {
+            key: range_filter.key.clone(),
+            field_type,
+            comparison: range_filter.comparison,
+            value: FieldValue::parse(&range_filter.value, field_type),
+        }
+    }
+
+    fn matches(&self, field_value: Option<&String>) -> bool {
+        let ordering = match (field_value, &self.value) {
+            (Some(field_value), Some(value)) => FieldValue::parse(field_value, self.field_type)
+                .and_then(|field_value| field_value.compare(value)),
+            _ => None,
+        };
+
+        match ordering {
+            Some(ordering) => match self.comparison {
+                Comparison::LessThan => ordering == Ordering::Less,
+                Comparison::LessThanOrEqual => ordering != Ordering::Greater,
+                Comparison::Equal => ordering == Ordering::Equal,
+                Comparison::GreaterThanOrEqual => ordering != Ordering::Less,
+                Comparison::GreaterThan => ordering == Ordering::Greater,
+            },
+            None => false,
+        }
+    }
+}
+
+impl EntryFilter {
+    pub(super) fn new(
+        query_filters: &[FieldFilter],
+        options: &SearchOptions,
+        field_types: &BTreeMap<String, FieldType>,
+    ) -> Self {
+        let mut entry_filter = EntryFilter::default();
+
+        for filter in query_filters {
+            let values = if filter.excluded {
+                &mut entry_filter.rejected_values
+            } else {
+                &mut entry_filter.accepted_values
+            };
+
+            values
+                .entry(filter.key.clone())
+                .or_default()
+                .insert(filter.value.clone());
+        }
+
+        for (key, values) in &options.filters {
+            entry_filter
+                .accepted_values
+                .entry(key.to_lowercase())
+                .or_default()
+                .extend(values.iter().map(|value| value.to_lowercase()));
+        }
+
+        entry_filter.ranges = options
+            .range_filters
+            .iter()
+            .map(|range_filter| FieldRange::new(range_filter, field_types))
+            .collect();
+
+        entry_filter
+    }
+
+    pub(super) fn is_empty(&self) -> bool {
+        self.accepted_values.is_empty() && self.rejected_values.is_empty() && self.ranges.is_empty()
+    }
+
+    pub(super) fn matches(&self, fields: &Fields) -> bool {
+        let fields: BTreeMap<String, String> = fields
+            .iter()
+            .map(|(key, value)| (key.to_lowercase(), value.to_lowercase()))
+            .collect();
+
+        let is_accepted = self
+            .accepted_values
+            .iter()
+            .all(|(key, values)| fields.get(key).is_some_and(|value| values.contains(value)));
+
+        let is_rejected = self
+            .rejected_values
+            .iter()
+            .any(|(key, values)| fields.get(key).is_some_and(|value| values.contains(value)));
+
+        let is_in_ranges = self
+            .ranges
+            .iter()
+            .all(|range| range.matches(fields.get(&range.key)));
+
+        is_accepted && !is_rejected && is_in_ranges
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{collections::HashMap, convert::TryFrom};
+
+    fn fields(pairs: &[(&str, &str)]) -> Fields {
+        pairs
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+            .collect()
+    }
+
+    fn filter(key: &str, value: &str, excluded: bool) -> FieldFilter {
+        FieldFilter {
+            key: key.to_string(),
+            value: value.to_string(),
+            excluded,
+        }
+    }
+
+    #[test]
+    fn values_for_the_same_key_are_alternatives() {
+        let entry_filter = EntryFilter::new(
+            &[
+                filter("category", "guide", false),
+                filter("category", "api", false),
+            ],
+            &SearchOptions::default(),
+            &BTreeMap::new(),
+        );
+
+        assert!(entry_filter.matches(&fields(&[("category", "Guide")])));
+        assert!(entry_filter.matches(&fields(&[("category", "api")])));
+        assert!(!entry_filter.matches(&fields(&[("category", "changelog")])));
+        assert!(!entry_filter.matches(&fields(&[])));
+    }
+
+    #[test]
+    fn different_keys_must_all_match() {
+        let entry_filter = EntryFilter::new(
+            &[filter("category", "guide", false)],
+            &SearchOptions {
+                filters: HashMap::from([("Version".to_string(), vec!["2".to_string()])]),
+                ..SearchOptions::default()
+            },
+            &BTreeMap::new(),
+        );
+
+        assert!(entry_filter.matches(&fields(&[("category", "guide"), ("version", "2")])));
+        assert!(!entry_filter.matches(&fields(&[("category", "guide"), ("version", "1")])));
+    }
+
+    #[test]
+    fn excluded_values_reject_entries() {
+        let entry_filter = EntryFilter::new(
+            &[filter("category", "changelog", true)],
+            &SearchOptions::default(),
+            &BTreeMap::new(),
+        );
+
+        assert!(entry_filter.matches(&fields(&[])));
+        assert!(entry_filter.matches(&fields(&[("category", "guide")])));
+        assert!(!entry_filter.matches(&fields(&[("category", "changelog")])));
+    }
+
+    fn range_filter(string: &str) -> RangeFilter {
+        RangeFilter::try_from(string.to_string()).unwrap()
+    }
+
+    #[test]
+    fn range_filters_compare_by_declared_type() {
+        let field_types = BTreeMap::from([
+            ("date".to_string(), FieldType::Date),
+            ("weight".to_string(), FieldType::Number),
+        ]);
+        let entry_filter = EntryFilter::new(
+            &[],
+            &SearchOptions {
+                range_filters: vec![
+                    range_filter("date >= 2024-01-01"),
+                    range_filter("weight < 10"),
+                ],
+                ..SearchOptions::default()
+            },
+            &field_types,
+        );
+
+        assert!(entry_filter.matches(&fields(&[("date", "2024-01-01"), ("weight", "9")])));
+        assert!(entry_filter.matches(&fields(&[
+            ("Date", "2024-03-05T10:00:00Z"),
+            ("weight", "-2.5")
+        ])));
+        assert!(!entry_filter.matches(&fields(&[("date", "2023-12-31"), ("weight", "9")])));
+        assert!(!entry_filter.matches(&fields(&[("date", "2024-01-01"), ("weight", "10")])));
+        assert!(!entry_filter.matches(&fields(&[("date", "soon"), ("weight", "9")])));
+        assert!(!entry_filter.matches(&fields(&[("weight", "9")])));
+    }
+
+    #[test]
+    fn undeclared_fields_compare_as_keywords() {
+        let entry_filter = EntryFilter::new(
+            &[],
+            &SearchOptions {
+                range_filters: vec![range_filter("version <= B")],
+                ..SearchOptions::default()
+            },
+            &BTreeMap::new(),
+        );
+
+        assert!(entry_filter.matches(&fields(&[("version", "a")])));
+        assert!(entry_filter.matches(&fields(&[("version", "b")])));
+        assert!(!entry_filter.matches(&fields(&[("version", "c")])));
+    }
+
+    #[test]
+    fn unparseable_range_filter_values_match_nothing() {
+        let entry_filter = EntryFilter::new(
+            &[],
+            &SearchOptions {
+                range_filters: vec![range_filter("date > yesterday")],
+                ..SearchOptions::default()
+            },
+            &BTreeMap::from([("date".to_string(), FieldType::Date)]),
+        );
+
+        assert!(!entry_filter.matches(&fields(&[("date", "2024-01-01")])));
+    }
+}
@@ -0,0 +1,147 @@
+use crate::V3Index as Index;
+
+use super::super::scores::FUZZY_SCORE;
+use super::get_container_intermediate_excerpts;
+use super::intermediate_excerpt::IntermediateExcerpt;
+
+/**
+ * The number of typos that are tolerated in a word of a given length. Short
+ * words aren't matched fuzzily at all, since nearly every other short word
+ * would be within one or two edits of them.
+ */
+pub(super) fn max_edit_distance(word_length: usize) -> usize {
+    match word_length {
+        0..=3 => 0,
+        4..=7 => 1,
+        _ => 2,
+    }
+}
+
+/**
+ * Whether `key` has few enough more or fewer characters than a word of
+ * `word_length` characters to possibly be within `max_distance` edits of it.
+ * Checking this first keeps most of the index's vocabulary from being copied
+ * into a `Vec<char>` just to be thrown away.
+ */
+pub(super) fn length_is_within_edit_distance(
+    key: &str,
+    word_length: usize,
+    max_distance: usize,
+) -> bool {
+    // Every character is at least one byte, so keys with too few bytes can
+    // be skipped without counting their characters.
+    key.len() + max_distance >= word_length
+        && key.chars().count().abs_diff(word_length) <= max_distance
+}
+
+/**
+ * Computes the Levenshtein distance between two words, giving up and
+ * returning `None` as soon as it's clear the distance is over `max_distance`.
+ */
+pub(super) fn edit_distance(a: &[char], b: &[char], max_distance: usize) -> Option<usize> {
+    if a.len().abs_diff(b.len()) > max_distance {
+        return None;
+    }
+
+    let mut previous_row: Vec<usize> = (0..=b.len()).collect();
+    let mut current_row: Vec<usize> = vec![0; b.len() + 1];
+
+    for (i, a_char) in a.iter().enumerate() {
+        current_row[0] = i + 1;
+
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution_cost = usize::from(a_char != b_char);
+            current_row[j + 1] = (previous_row[j] + substitution_cost)
+                .min(previous_row[j + 1] + 1)
+                .min(current_row[j] + 1);
+        }
+
+        if current_row.iter().all(|distance| *distance > max_distance) {
+            return None;
+        }
+
+        std::mem::swap(&mut previous_row, &mut current_row);
+    }
+
+    Some(previous_row[b.len()]).filter(|distance| *distance <= max_distance)
+}
+
+/**
+ * Finds the words in the index that are within a few typos of the query word,
+ * and returns intermediate excerpts for each of them. Closer matches score
+ * higher.
+ */
+pub(super) fn get_intermediate_excerpts(index: &Index, word: &str) -> Vec<IntermediateExcerpt> {
+    let word_chars: Vec<char> = word.chars().collect();
+    let max_distance = max_edit_distance(word_chars.len());
+
+    if max_distance == 0 {
+        return vec![];
+    }
+
+    let mut output = vec![];
+
+    // Containers with no results only exist to hold aliases for prefixes and
+    // stems, so they aren't real words to match against.
+    for (key, container) in index.containers.iter().filter(|(key, container)| {
+        !container.results.is_empty()
+            && length_is_within_edit_distance(key, word_chars.len(), max_distance)
+    }) {
+        let key_chars: Vec<char> = key.chars().collect();
+        let Some(distance) = edit_distance(&word_chars, &key_chars, max_distance) else {
+            continue;
+        };
+
+        #[allow(clippy::cast_possible_truncation)]
+        let score = FUZZY_SCORE / (distance as u8);
+
+        output.extend(get_container_intermediate_excerpts(key, container, score));
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn distance(a: &str, b: &str, max_distance: usize) -> Option<usize> {
+        let a: Vec<char> = a.chars().collect();
+        let b: Vec<char> = b.chars().collect();
+        edit_distance(&a, &b, max_distance)
+    }
+
+    #[test]
+    fn edit_distance_counts_insertions_deletions_and_substitutions() {
+        assert_eq!(distance("goverment", "government", 2), Some(1));
+        assert_eq!(distance("government", "goverment", 2), Some(1));
+        assert_eq!(distance("liberty", "libirty", 2), Some(1));
+        assert_eq!(distance("liberty", "libxrtz", 2), Some(2));
+        assert_eq!(distance("liberty", "liberty", 2), Some(0));
+        assert_eq!(distance("caf\u{e9}", "cafe", 1), Some(1));
+    }
+
+    #[test]
+    fn edit_distance_gives_up_past_maximum() {
+        assert_eq!(distance("liberty", "library", 1), None);
+        assert_eq!(distance("cat", "catastrophe", 2), None);
+    }
+
+    #[test]
+    fn words_with_too_different_lengths_are_skipped() {
+        assert!(length_is_within_edit_distance("government", 9, 1));
+        assert!(length_is_within_edit_distance("caf\u{e9}", 4, 1));
+        assert!(length_is_within_edit_distance("東京都", 2, 1));
+        assert!(!length_is_within_edit_distance("東京都", 5, 1));
+        assert!(!length_is_within_edit_distance("cat", 8, 2));
+        assert!(!length_is_within_edit_distance("catastrophe", 8, 2));
+    }
+
+    #[test]
+    fn short_words_are_not_fuzzy_matched() {
+        assert_eq!(max_edit_distance(3), 0);
+        assert_eq!(max_edit_distance(4), 1);
+        assert_eq!(max_edit_distance(8), 2);
+    }
+}
@@ -7,9 +7,28 @@ use intermediate_excerpt::IntermediateExcerpt;
 mod entry_and_intermediate_excerpts;
 use entry_and_intermediate_excerpts::EntryAndIntermediateExcerpts;
 
+mod bm25;
+mod evaluate;
+mod field_value;
+mod fuzzy;
+mod phrase;
+mod query;
+mod stemming;
+
+mod filters;
+use filters::EntryFilter;
+
+mod sorting;
+mod suggestions;
+mod title_match;
+use suggestions::{get_suggestions, MAX_HIT_COUNT_FOR_SUGGESTIONS};
+
+use crate::config::{Ranking, Tokenizer};
 use crate::stopwords;
+use crate::Facets;
 use crate::Output;
 use crate::Result;
+use crate::SearchOptions;
 use crate::V3Index as Index;
 
 use super::scores::STOPWORD_SCORE;
@@ -20,26 +39,24 @@ use super::EntryIndex;
 use super::Score;
 use super::SearchResult;
 
-pub fn search(index: &Index, query: &str) -> Output {
-    let normalized_query = query.to_lowercase();
-    let words_in_query: Vec<String> = normalized_query
-        .split(|c| c == ' ' || c == '-')
-        .map(ToString::to_string)
-        .collect();
+pub fn search(index: &Index, query: &str, options: &SearchOptions) -> Output {
+    let mut parsed_query = query::parse(query, |key| index.config.field_keys.contains(key));
+    parsed_query.normalize_words(index.config.normalization);
 
-    // Get the containers for each word in the query, and separate them
+    // Entries are split with their own tokenizer, so if any of them were
+    // split into CJK bigrams, the query's words are looked for as bigrams too.
+    if index.config.uses_cjk_bigrams {
+        parsed_query.tokenize_words(Tokenizer::CJKBigram);
+    }
+
+    // Get the containers for each term in the query, and separate them
     // into intermediate excerpts
-    let mut intermediate_excerpts: Vec<IntermediateExcerpt> = words_in_query
-        .iter()
-        .filter_map(|word| index.containers.get_key_value(word))
-        .map(|(word, ctr)| ContainerWithQuery::new(ctr.clone(), word))
-        .flat_map(|ctr_query| ctr_query.get_intermediate_excerpts(index))
-        .collect();
+    let mut intermediate_excerpts = evaluate::evaluate(index, &parsed_query.clauses, options);
 
-    for mut ie in &mut intermediate_excerpts {
-        if stopwords.contains(&ie.query.as_str()) {
-            ie.score = STOPWORD_SCORE;
-        }
+    let entry_filter = EntryFilter::new(&parsed_query.filters, options, &index.config.field_types);
+    if !entry_filter.is_empty() {
+        intermediate_excerpts
+            .retain(|ie| entry_filter.matches(&index.entries[ie.entry_index].fields));
     }
 
     let mut excerpts_by_index: BTreeMap<EntryIndex, Vec<IntermediateExcerpt>> = BTreeMap::new();
@@ -51,6 +68,22 @@ pub fn search(index: &Index, query: &str) -> Output {
     }
 
     let total_len = &excerpts_by_index.len();
+    let facets = get_facet_counts(index, &excerpts_by_index);
+
+    let suggestions = if *total_len <= MAX_HIT_COUNT_FOR_SUGGESTIONS {
+        get_suggestions(index, query, &parsed_query)
+    } else {
+        vec![]
+    };
+
+    let query_words: Vec<String> = parsed_query
+        .words()
+        .into_iter()
+        .map(ToString::to_string)
+        .collect();
+    let last_query_word_is_prefix = query_words
+        .last()
+        .is_some_and(|word| title_match::can_match_title_prefixes(index, word));
 
     let mut output_results: Vec<Result> = excerpts_by_index
         .iter()
@@ -59,19 +92,147 @@ pub fn search(index: &Index, query: &str) -> Output {
                 entry: index.entries[*entry_index].clone(),
                 config: index.config.clone(),
                 intermediate_excerpts: ies.clone(),
+                query_words: query_words.clone(),
+                last_query_word_is_prefix,
+                bm25_score: match index.config.ranking {
+                    Ranking::Legacy => None,
+                    Ranking::BM25 => Some(bm25::score(index, *entry_index, ies)),
+                },
             };
             Result::from(data)
         })
         .collect();
     output_results.sort_by_key(|or| or.entry.title.clone());
     output_results.sort_by_key(|or| -(or.score as i64));
-    output_results.truncate(index.config.displayed_results_count as usize);
+
+    if let Some(sort_order) = &options.sort {
+        sorting::sort_results(&mut output_results, sort_order, &index.config.field_types);
+    }
+
+    let limit = options
+        .limit
+        .unwrap_or(index.config.displayed_results_count as usize);
+    let output_results: Vec<Result> = output_results
+        .into_iter()
+        .skip(options.offset)
+        .take(limit)
+        .collect();
 
     Output {
         results: output_results,
         total_hit_count: *total_len,
         url_prefix: index.config.url_prefix.clone(),
+        facets,
+        suggestions,
+    }
+}
+
+fn get_facet_counts(
+    index: &Index,
+    excerpts_by_index: &BTreeMap<EntryIndex, Vec<IntermediateExcerpt>>,
+) -> Option<Facets> {
+    if index.facets.is_empty() {
+        return None;
+    }
+
+    let facets = index
+        .facets
+        .iter()
+        .map(|(key, facet_values)| {
+            let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+            for entry_index in excerpts_by_index.keys() {
+                if let Some(Some(value_index)) = facet_values.entry_values.get(*entry_index) {
+                    *counts
+                        .entry(facet_values.values[*value_index].clone())
+                        .or_default() += 1;
+                }
+            }
+            (key.clone(), counts)
+        })
+        .collect();
+
+    Some(facets)
+}
+
+fn get_word_intermediate_excerpts(
+    index: &Index,
+    word: &str,
+    options: &SearchOptions,
+) -> Vec<IntermediateExcerpt> {
+    let mut intermediate_excerpts: Vec<IntermediateExcerpt> =
+        match index.containers.get_key_value(word) {
+            Some((word, ctr)) => {
+                ContainerWithQuery::new(ctr.clone(), word).get_intermediate_excerpts(index)
+            }
+            // The index left this stopword out, so don't look for other
+            // words that look like it
+            None if is_stopword(index, word) => vec![],
+            None => {
+                let stemmed = stemming::get_intermediate_excerpts(index, word);
+                if stemmed.is_empty() && options.fuzzy {
+                    fuzzy::get_intermediate_excerpts(index, word)
+                } else {
+                    stemmed
+                }
+            }
+        };
+
+    for ie in &mut intermediate_excerpts {
+        if is_stopword(index, &ie.query) {
+            ie.score = STOPWORD_SCORE;
+        }
+    }
+
+    intermediate_excerpts
+}
+
+/**
+ * Checks the index's own stopword list, falling back to the built-in English
+ * list for indexes that don't have one.
+ */
+fn is_stopword(index: &Index, word: &str) -> bool {
+    match &index.config.stopwords {
+        Some(index_stopwords) => index_stopwords.iter().any(|stopword| stopword == word),
+        None => stopwords.contains(&word),
+    }
+}
+
+/**
+ * Returns an intermediate excerpt for each place a container's word appears,
+ * all with the same score. Entries indexed without excerpts get a single
+ * intermediate excerpt each.
+ */
+fn get_container_intermediate_excerpts(
+    key: &str,
+    container: &Container,
+    score: Score,
+) -> Vec<IntermediateExcerpt> {
+    let mut output = vec![];
+
+    for (entry_index, result) in &container.results {
+        if result.excerpts.is_empty() {
+            output.push(IntermediateExcerpt {
+                query: key.to_string(),
+                entry_index: *entry_index,
+                score,
+                ..IntermediateExcerpt::default()
+            });
+        }
+
+        for excerpt in &result.excerpts {
+            output.push(IntermediateExcerpt {
+                query: key.to_string(),
+                entry_index: *entry_index,
+                score,
+                source: excerpt.source,
+                word_index: excerpt.word_index,
+                internal_annotations: excerpt.internal_annotations.clone(),
+                fields: excerpt.fields.clone(),
+            });
+        }
     }
+
+    output
 }
 
 #[derive(Debug)]
@@ -167,6 +328,10 @@ impl From<Entry> for crate::Entry {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::{StemmingConfig, StopwordsConfig, Synonyms};
+    use crate::index_v3::scores::{FUZZY_SCORE, STEM_SCORE};
+    use crate::index_v3::test_helpers::{build_index, build_numbered_index, result_titles};
+    use crate::HighlightRange;
     use pretty_assertions::assert_eq;
     use std::convert::TryFrom;
     use std::fs;
@@ -180,9 +345,494 @@ mod tests {
         let _bytes_read = buf_reader.read_to_end(&mut index_bytes);
 
         let index = Index::try_from(index_bytes.as_slice()).unwrap();
-        let generated = search(&index, "liber old world");
+        let generated = search(&index, "liber old world", &SearchOptions::default());
         let expected = serde_json::from_str("{\"results\":[{\"entry\":{\"url\":\"https://www.congress.gov/resources/display/content/The+Federalist+Papers#TheFederalistPapers-1\",\"title\":\"Introduction\",\"fields\":{}},\"excerpts\":[{\"text\":\"in many respects the most interesting in the world. It has been frequently remarked that it\",\"highlight_ranges\":[{\"beginning\":45,\"end\":51}],\"score\":128,\"internal_annotations\":[],\"fields\":{}},{\"text\":\"despotic power and hostile to the principles of liberty. An over-scrupulous jealousy of danger to the\",\"highlight_ranges\":[{\"beginning\":48,\"end\":56}],\"score\":125,\"internal_annotations\":[],\"fields\":{}},{\"text\":\"of love, and that the noble enthusiasm of liberty is apt to be infected with a\",\"highlight_ranges\":[{\"beginning\":42,\"end\":49}],\"score\":125,\"internal_annotations\":[],\"fields\":{}},{\"text\":\"of government is essential to the security of liberty; that, in the contemplation of a sound\",\"highlight_ranges\":[{\"beginning\":46,\"end\":54}],\"score\":125,\"internal_annotations\":[],\"fields\":{}},{\"text\":\"that this is the safest course for your liberty, your dignity, and your happiness. I affect\",\"highlight_ranges\":[{\"beginning\":40,\"end\":48}],\"score\":125,\"internal_annotations\":[],\"fields\":{}}],\"title_highlight_ranges\":[],\"score\":878}],\"total_hit_count\":1,\"url_prefix\":\"\"}").unwrap();
 
         assert_eq!(generated, expected, "{:?}", generated);
     }
+
+    #[test]
+    fn quoted_phrase_only_matches_adjacent_words() {
+        let index = build_numbered_index(
+            &[
+                "The powers of the federal government are few and defined.",
+                "A government that ignores the federal principle.",
+            ],
+            |_| {},
+        );
+
+        let unquoted = search(&index, "federal government", &SearchOptions::default());
+        assert_eq!(unquoted.total_hit_count, 2);
+
+        let quoted = search(&index, "\"federal government\"", &SearchOptions::default());
+        assert_eq!(quoted.total_hit_count, 1);
+        assert_eq!(quoted.results[0].entry.title, "Document 0");
+        assert_eq!(
+            quoted.results[0].excerpts[0].highlight_ranges,
+            vec![
+                HighlightRange {
+                    beginning: 18,
+                    end: 25
+                },
+                HighlightRange {
+                    beginning: 26,
+                    end: 36
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn quoted_phrase_must_be_in_order() {
+        let index = build_numbered_index(&["The government is a federal one."], |_| {});
+        assert_eq!(
+            search(&index, "\"federal government\"", &SearchOptions::default()).total_hit_count,
+            0
+        );
+        assert_eq!(
+            search(&index, "\"a federal\"", &SearchOptions::default()).total_hit_count,
+            1
+        );
+    }
+
+    #[test]
+    fn required_and_excluded_terms_filter_results() {
+        let index = build_numbered_index(
+            &[
+                "Call animate to start the render loop.",
+                "The deprecated animate method still works.",
+                "The render loop runs every frame.",
+            ],
+            |_| {},
+        );
+
+        let titles = |query: &str| result_titles(search(&index, query, &SearchOptions::default()));
+
+        assert_eq!(
+            titles("animate render"),
+            vec!["Document 0", "Document 1", "Document 2"]
+        );
+        assert_eq!(titles("+animate render"), vec!["Document 0", "Document 1"]);
+        assert_eq!(titles("animate -deprecated"), vec!["Document 0"]);
+        assert_eq!(
+            titles("+render +(animate OR frame)"),
+            vec!["Document 0", "Document 2"]
+        );
+        assert_eq!(titles("-deprecated"), Vec::<String>::new());
+    }
+
+    #[test]
+    fn excluded_terms_only_match_the_word_itself() {
+        let index = build_numbered_index(
+            &[
+                "The api reference.",
+                "The apiary keeps bees.",
+                "The federal government.",
+            ],
+            |_| {},
+        );
+
+        let titles =
+            |query: &str, options: &SearchOptions| result_titles(search(&index, query, options));
+        let fuzzy = SearchOptions {
+            fuzzy: true,
+            ..SearchOptions::default()
+        };
+
+        assert_eq!(
+            titles("the -api", &SearchOptions::default()),
+            vec!["Document 1", "Document 2"]
+        );
+        assert_eq!(
+            titles("the -governmet", &fuzzy),
+            vec!["Document 0", "Document 1", "Document 2"]
+        );
+        assert_eq!(
+            titles("the -government", &fuzzy),
+            vec!["Document 0", "Document 1"]
+        );
+    }
+
+    #[test]
+    fn unparseable_query_falls_back_to_matching_any_word() {
+        let index = build_numbered_index(&["Call animate to start the render loop."], |_| {});
+        assert_eq!(
+            search(&index, "(animate OR", &SearchOptions::default()).total_hit_count,
+            1
+        );
+    }
+
+    #[test]
+    fn field_filters_restrict_results() {
+        let contents = "Configure the render loop.";
+        let index = build_index(
+            &[
+                ("Rendering", contents),
+                ("Renderer", contents),
+                ("1.2.0", contents),
+            ],
+            |config| {
+                for (file, category) in
+                    config
+                        .input
+                        .files
+                        .iter_mut()
+                        .zip(["Guide", "API", "Changelog"])
+                {
+                    file.fields = HashMap::from([("category".to_string(), category.to_string())]);
+                }
+            },
+        );
+
+        let titles =
+            |query: &str, options: &SearchOptions| result_titles(search(&index, query, options));
+        let no_options = SearchOptions::default();
+
+        assert_eq!(
+            titles("render category:guide", &no_options),
+            vec!["Rendering"]
+        );
+        assert_eq!(
+            titles("render category:guide category:api", &no_options),
+            vec!["Renderer", "Rendering"]
+        );
+        assert_eq!(
+            titles("render -category:changelog", &no_options),
+            vec!["Renderer", "Rendering"]
+        );
+
+        let api_only = SearchOptions {
+            filters: HashMap::from([("category".to_string(), vec!["API".to_string()])]),
+            ..SearchOptions::default()
+        };
+        assert_eq!(titles("render", &api_only), vec!["Renderer"]);
+        assert_eq!(search(&index, "render", &api_only).total_hit_count, 1);
+    }
+
+    #[test]
+    fn colons_in_words_that_are_not_fields_are_searched_for() {
+        let path = "Use std::vec for growable arrays.";
+        let time = "The meeting starts at 10:30 sharp.";
+        let index = build_index(&[(path, path), (time, time)], |config| {
+            for file in &mut config.input.files {
+                file.fields = HashMap::from([("category".to_string(), "Guide".to_string())]);
+            }
+        });
+
+        let titles = |query: &str| result_titles(search(&index, query, &SearchOptions::default()));
+        assert_eq!(
+            titles("std::vec"),
+            vec!["Use std::vec for growable arrays."]
+        );
+        assert_eq!(titles("10:30"), vec!["The meeting starts at 10:30 sharp."]);
+        assert_eq!(titles("meeting category:api"), Vec::<String>::new());
+    }
+
+    #[test]
+    fn facets_count_every_hit() {
+        let index = build_numbered_index(&["Configure the render loop."; 4], |config| {
+            for (file, category) in config
+                .input
+                .files
+                .iter_mut()
+                .zip(["Guide", "API", "API", "Guide"])
+            {
+                file.fields = HashMap::from([("category".to_string(), category.to_string())]);
+            }
+            config.output.displayed_results_count = 1;
+            config.output.facets = vec!["category".to_string(), "version".to_string()];
+        });
+
+        let output = search(&index, "render", &SearchOptions::default());
+        assert_eq!(output.results.len(), 1);
+        assert_eq!(
+            output.facets,
+            Some(Facets::from([
+                (
+                    "category".to_string(),
+                    BTreeMap::from([("API".to_string(), 2), ("Guide".to_string(), 2)])
+                ),
+                ("version".to_string(), BTreeMap::new()),
+            ]))
+        );
+
+        let output = search(&index, "render category:api", &SearchOptions::default());
+        assert_eq!(
+            output.facets.unwrap()["category"],
+            BTreeMap::from([("API".to_string(), 2)])
+        );
+    }
+
+    #[test]
+    fn index_without_facets_has_no_facet_output() {
+        let index = build_numbered_index(&["Configure the render loop."], |_| {});
+        assert_eq!(
+            search(&index, "render", &SearchOptions::default()).facets,
+            None
+        );
+    }
+
+    #[test]
+    fn offset_and_limit_paginate_results() {
+        let contents: Vec<String> = (0..25)
+            .map(|i| format!("{} render", "word ".repeat(i)))
+            .collect();
+        let contents: Vec<&str> = contents.iter().map(String::as_str).collect();
+        let index = build_numbered_index(&contents, |_| {});
+
+        let default_page = search(&index, "render", &SearchOptions::default());
+        assert_eq!(default_page.results.len(), 10);
+        assert_eq!(default_page.total_hit_count, 25);
+
+        let all_titles: Vec<String> = search(
+            &index,
+            "render",
+            &SearchOptions {
+                limit: Some(100),
+                ..SearchOptions::default()
+            },
+        )
+        .results
+        .into_iter()
+        .map(|r| r.entry.title)
+        .collect();
+        assert_eq!(all_titles.len(), 25);
+
+        let second_page = search(
+            &index,
+            "render",
+            &SearchOptions {
+                offset: 10,
+                limit: Some(10),
+                ..SearchOptions::default()
+            },
+        );
+        assert_eq!(second_page.total_hit_count, 25);
+        assert_eq!(
+            second_page
+                .results
+                .into_iter()
+                .map(|r| r.entry.title)
+                .collect::<Vec<String>>(),
+            all_titles[10..20]
+        );
+
+        let past_the_end = search(
+            &index,
+            "render",
+            &SearchOptions {
+                offset: 30,
+                ..SearchOptions::default()
+            },
+        );
+        assert!(past_the_end.results.is_empty());
+        assert_eq!(past_the_end.total_hit_count, 25);
+    }
+
+    #[test]
+    fn fuzzy_option_matches_misspelled_words() {
+        let index = build_numbered_index(
+            &[
+                "The powers of the federal government are few and defined.",
+                "Liberty is to faction what air is to fire.",
+            ],
+            |_| {},
+        );
+
+        let fuzzy = SearchOptions {
+            fuzzy: true,
+            ..SearchOptions::default()
+        };
+
+        assert_eq!(
+            search(&index, "goverment", &SearchOptions::default()).total_hit_count,
+            0
+        );
+
+        let output = search(&index, "goverment", &fuzzy);
+        assert_eq!(result_titles(output), vec!["Document 0"]);
+
+        // Words that exist in the index aren't fuzzy matched
+        let output = search(&index, "fire", &fuzzy);
+        assert_eq!(result_titles(output), vec!["Document 1"]);
+    }
+
+    #[test]
+    fn fuzzy_matches_score_below_exact_matches() {
+        let index = build_numbered_index(
+            &["The federal government.", "The federal governments."],
+            |_| {},
+        );
+
+        let output = search(
+            &index,
+            "governmens",
+            &SearchOptions {
+                fuzzy: true,
+                ..SearchOptions::default()
+            },
+        );
+
+        assert_eq!(output.total_hit_count, 2);
+        assert!(output
+            .results
+            .iter()
+            .all(|result| result.excerpts[0].score <= usize::from(FUZZY_SCORE)));
+    }
+
+    #[test]
+    fn suggestions_correct_misspelled_words() {
+        let index = build_numbered_index(
+            &[
+                "Liberty is to faction what air is to fire.",
+                "The blessings of liberty.",
+                "A library of laws, and the liberty to change them.",
+                "The federal government.",
+            ],
+            |_| {},
+        );
+
+        let output = search(&index, "libirty goverment", &SearchOptions::default());
+        assert_eq!(output.total_hit_count, 0);
+        assert_eq!(output.suggestions, vec!["liberty government"]);
+    }
+
+    #[test]
+    fn suggestions_prefer_more_common_words() {
+        let index = build_numbered_index(
+            &[
+                "The blessings of libery.",
+                "The blessings of liberty.",
+                "Liberty is to faction what air is to fire.",
+            ],
+            |_| {},
+        );
+
+        let output = search(&index, "libery", &SearchOptions::default());
+        assert_eq!(output.total_hit_count, 1);
+        assert_eq!(output.suggestions, vec!["liberty"]);
+    }
+
+    #[test]
+    fn suggestions_keep_operators_and_filters_as_written() {
+        let index = build_numbered_index(
+            &["The federal government.", "The blessings of liberty."],
+            |config| {
+                for file in &mut config.input.files {
+                    file.fields = HashMap::from([("category".to_string(), "Guide".to_string())]);
+                }
+            },
+        );
+
+        let output = search(
+            &index,
+            "+Goverment (libery OR fire) Category:Guid section:\"Big Goverment\"",
+            &SearchOptions::default(),
+        );
+        assert_eq!(output.total_hit_count, 0);
+        assert_eq!(
+            output.suggestions,
+            vec!["+government (liberty OR fire) Category:Guid section:\"Big Goverment\""]
+        );
+    }
+
+    #[test]
+    fn queries_with_many_results_have_no_suggestions() {
+        let index = build_numbered_index(&["liberty", "liberty", "liberty", "libery"], |_| {});
+        let output = search(&index, "liberty", &SearchOptions::default());
+        assert!(output.suggestions.is_empty());
+
+        // A prefix of a word in the index isn't a misspelling
+        let output = search(&index, "libert", &SearchOptions::default());
+        assert!(output.suggestions.is_empty());
+    }
+
+    #[test]
+    fn query_words_missing_from_the_index_are_stemmed() {
+        let index = build_numbered_index(
+            &[
+                "The people governed themselves.",
+                "A happy and peaceful nation.",
+                "Nothing relevant here.",
+            ],
+            |_| {},
+        );
+
+        let output = search(&index, "governing", &SearchOptions::default());
+        assert_eq!(output.results[0].excerpts[0].score, STEM_SCORE as usize);
+        assert_eq!(result_titles(output), vec!["Document 0"]);
+
+        // Stemming can change the stem's last letter
+        let output = search(&index, "happiness", &SearchOptions::default());
+        assert_eq!(result_titles(output), vec!["Document 1"]);
+    }
+
+    #[test]
+    fn query_words_are_not_stemmed_without_a_stemming_algorithm() {
+        let index = build_numbered_index(&["The people governed themselves."], |config| {
+            config.input.stemming = StemmingConfig::None;
+        });
+
+        let output = search(&index, "governing", &SearchOptions::default());
+        assert_eq!(output.total_hit_count, 0);
+    }
+
+    #[test]
+    fn stopwords_come_from_the_configured_language() {
+        let contents = ["Les oiseaux chantent.", "The birds sing."];
+        let score = |index: &Index, query: &str| {
+            search(index, query, &SearchOptions::default()).results[0].score
+        };
+
+        let english_index = build_numbered_index(&contents, |_| {});
+        assert!(score(&english_index, "les") > score(&english_index, "the"));
+
+        let french_index = build_numbered_index(&contents, |config| {
+            config.input.stopwords =
+                Some(StopwordsConfig::Language(rust_stemmers::Algorithm::French));
+        });
+        assert!(score(&french_index, "the") > score(&french_index, "les"));
+    }
+
+    #[test]
+    fn stopwords_can_be_removed_from_the_index() {
+        let index = build_numbered_index(&["The birds sing.", "Birds sleep."], |config| {
+            config.input.stopwords = Some(StopwordsConfig::Custom(vec![
+                "The".to_string(),
+                "sing".to_string(),
+            ]));
+            config.input.remove_stopwords = true;
+        });
+
+        assert!(!index.containers.contains_key("the"));
+        assert!(!index.containers.contains_key("sing"));
+
+        let titles = |query: &str| result_titles(search(&index, query, &SearchOptions::default()));
+        assert_eq!(titles("the"), Vec::<String>::new());
+        assert_eq!(titles("sing"), Vec::<String>::new());
+        assert_eq!(titles("sleep"), vec!["Document 1"]);
+
+        let output = search(&index, "birds", &SearchOptions::default());
+        assert_eq!(output.results[0].excerpts[0].text, "The birds sing.");
+    }
+
+    #[test]
+    fn synonyms_find_each_other() {
+        let index = build_numbered_index(
+            &["Deploying to Kubernetes", "Deploying with k8s"],
+            |config| {
+                config.input.synonyms =
+                    [("k8s".to_string(), Synonyms::Word("kubernetes".to_string()))]
+                        .into_iter()
+                        .collect();
+            },
+        );
+
+        let titles = |query: &str| result_titles(search(&index, query, &SearchOptions::default()));
+        assert_eq!(titles("k8s"), vec!["Document 0", "Document 1"]);
+        assert_eq!(titles("kubernetes"), vec!["Document 0", "Document 1"]);
+
+        let output = search(&index, "k8s", &SearchOptions::default());
+        assert_eq!(output.results[0].entry.title, "Document 1");
+    }
 }
@@ -0,0 +1,155 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::V3Index as Index;
+
+use super::super::{EntryIndex, WordListSource};
+use super::intermediate_excerpt::IntermediateExcerpt;
+use super::is_stopword;
+
+type WordPosition = (EntryIndex, WordListSource, usize);
+
+/**
+ * Finds every place in the index where the words of a phrase appear next to
+ * each other, in order, and returns an intermediate excerpt for each word of
+ * each occurrence.
+ *
+ * Only exact container matches are considered; prefix and stem aliases would
+ * make "next to each other" too loose to be useful.
+ *
+ * If the index was built without excerpts, word positions aren't available, so
+ * an entry matches the phrase if it contains every word in the phrase.
+ *
+ * If stopwords were left out of the index, they can't be matched, but they
+ * still took up a position in the document, so the phrase's other words are
+ * looked for the same distance apart as they are in the phrase.
+ */
+pub(super) fn get_intermediate_excerpts(
+    index: &Index,
+    words: &[String],
+) -> Vec<IntermediateExcerpt> {
+    let (offsets, words): (Vec<usize>, Vec<&String>) = words
+        .iter()
+        .enumerate()
+        .filter(|(_, word)| !(index.config.remove_stopwords && is_stopword(index, word)))
+        .unzip();
+
+    let mut containers = vec![];
+    for word in &words {
+        match index.containers.get(*word) {
+            Some(container) => containers.push(container),
+            None => return vec![],
+        }
+    }
+
+    if index.config.excerpts_per_result == 0 {
+        let entries_with_every_word = containers
+            .iter()
+            .map(|container| container.results.keys().copied().collect::<BTreeSet<_>>())
+            .reduce(|acc, entries| acc.intersection(&entries).copied().collect())
+            .unwrap_or_default();
+
+        return entries_with_every_word
+            .into_iter()
+            .flat_map(|entry_index| {
+                words
+                    .iter()
+                    .zip(&containers)
+                    .map(move |(word, container)| IntermediateExcerpt {
+                        query: (*word).clone(),
+                        entry_index,
+                        score: container.results[&entry_index].score,
+                        source: WordListSource::Contents,
+                        ..IntermediateExcerpt::default()
+                    })
+            })
+            .collect();
+    }
+
+    let positions_per_word: Vec<BTreeMap<WordPosition, IntermediateExcerpt>> = words
+        .iter()
+        .zip(&containers)
+        .map(|(word, container)| {
+            let mut positions = BTreeMap::new();
+            for (entry_index, result) in &container.results {
+                for excerpt in &result.excerpts {
+                    positions.insert(
+                        (*entry_index, excerpt.source, excerpt.word_index),
+                        IntermediateExcerpt {
+                            query: (*word).clone(),
+                            entry_index: *entry_index,
+                            score: result.score,
+                            source: excerpt.source,
+                            word_index: excerpt.word_index,
+                            internal_annotations: excerpt.internal_annotations.clone(),
+                            fields: excerpt.fields.clone(),
+                        },
+                    );
+                }
+            }
+            positions
+        })
+        .collect();
+
+    let mut output = vec![];
+
+    let Some(first_positions) = positions_per_word.first() else {
+        return output;
+    };
+
+    for &(entry_index, source, first_word_index) in first_positions.keys() {
+        let phrase_positions: Vec<WordPosition> = offsets
+            .iter()
+            .map(|offset| (entry_index, source, first_word_index + offset - offsets[0]))
+            .collect();
+
+        let is_match = phrase_positions
+            .iter()
+            .zip(&positions_per_word)
+            .all(|(position, positions)| positions.contains_key(position));
+
+        if is_match {
+            output.extend(
+                phrase_positions
+                    .iter()
+                    .zip(&positions_per_word)
+                    .map(|(position, positions)| positions[position].clone()),
+            );
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::index_v3::search::search;
+    use crate::index_v3::test_helpers::{build_numbered_index, result_titles};
+    use crate::SearchOptions;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn phrases_skip_stopwords_that_were_removed_from_the_index() {
+        let contents = ["The birds sing in the trees.", "Birds of the trees sing."];
+        let titles = |remove_stopwords, query: &str| {
+            let index = build_numbered_index(&contents, |config| {
+                config.input.remove_stopwords = remove_stopwords;
+            });
+            result_titles(search(&index, query, &SearchOptions::default()))
+        };
+
+        for remove_stopwords in [false, true] {
+            assert_eq!(
+                titles(remove_stopwords, "\"the birds sing\""),
+                vec!["Document 0"]
+            );
+            assert_eq!(
+                titles(remove_stopwords, "\"sing in the trees\""),
+                vec!["Document 0"]
+            );
+            assert_eq!(
+                titles(remove_stopwords, "\"birds the trees\""),
+                Vec::<String>::new()
+            );
+        }
+    }
+}
@@ -0,0 +1,819 @@
+use crate::config::{Normalization, Tokenizer};
+use crate::index_v3::{normalization::normalize, tokenizer::query_terms};
+
+/**
+ * A single unit of a search query. Unquoted words are matched on their own,
+ * while quoted text is matched as a phrase, meaning its words have to appear
+ * next to each other (and in order) in the document.
+ */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) enum QueryTerm {
+    Word(String),
+    Phrase(Vec<String>),
+}
+
+/**
+ * How a clause affects whether an entry is part of the results.
+ *
+ * If a query has any `Required` clauses, its `Optional` clauses only
+ * contribute to an entry's score. Otherwise, an entry has to match at least
+ * one `Optional` clause.
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum Occurrence {
+    Optional,
+    Required,
+    Excluded,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) enum Expression {
+    Term(QueryTerm),
+    Or(Vec<Expression>),
+    Group(Vec<Clause>),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) struct Clause {
+    pub(super) occurrence: Occurrence,
+    pub(super) expression: Expression,
+}
+
+impl Clause {
+    fn optional_term(term: QueryTerm) -> Self {
+        Clause {
+            occurrence: Occurrence::Optional,
+            expression: Expression::Term(term),
+        }
+    }
+}
+
+/**
+ * A `key:value` restriction on the fields of the entries that should be
+ * returned. Prefixing the filter with `-` removes matching entries instead.
+ */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) struct FieldFilter {
+    pub(super) key: String,
+    pub(super) value: String,
+    pub(super) excluded: bool,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(super) struct ParsedQuery {
+    pub(super) clauses: Vec<Clause>,
+    pub(super) filters: Vec<FieldFilter>,
+}
+
+impl ParsedQuery {
+    /**
+     * Normalizes every searched-for word the same way the index's words were
+     * normalized when it was built. Field filters are left as they are, since
+     * they're compared against entries' fields rather than the index's words.
+     */
+    pub(super) fn normalize_words(&mut self, normalization: Normalization) {
+        fn normalize_expression(expression: &mut Expression, normalization: Normalization) {
+            match expression {
+                Expression::Term(QueryTerm::Word(word)) => *word = normalize(word, normalization),
+                Expression::Term(QueryTerm::Phrase(phrase)) => {
+                    for word in phrase {
+                        *word = normalize(word, normalization);
+                    }
+                }
+                Expression::Or(expressions) => {
+                    for expression in expressions {
+                        normalize_expression(expression, normalization);
+                    }
+                }
+                Expression::Group(clauses) => {
+                    for clause in clauses {
+                        normalize_expression(&mut clause.expression, normalization);
+                    }
+                }
+            }
+        }
+
+        for clause in &mut self.clauses {
+            normalize_expression(&mut clause.expression, normalization);
+        }
+    }
+
+    /**
+     * Splits each searched-for word into the terms it was indexed as by
+     * entries split with `tokenizer`. A word that the tokenizer splits into
+     * several terms (like an unspaced run of CJK characters) becomes a
+     * phrase, since its terms have to appear next to each other. Entries
+     * split with `Tokenizer::Standard` indexed the word whole, so it's still
+     * searched for as written too.
+     */
+    pub(super) fn tokenize_words(&mut self, tokenizer: Tokenizer) {
+        fn tokenize_expression(expression: &mut Expression, tokenizer: Tokenizer) {
+            match expression {
+                Expression::Term(term) => {
+                    let words = match term {
+                        QueryTerm::Word(word) => std::slice::from_ref(word),
+                        QueryTerm::Phrase(phrase) => phrase.as_slice(),
+                    };
+                    let mut terms: Vec<String> = words
+                        .iter()
+                        .flat_map(|word| query_terms(word, tokenizer))
+                        .collect();
+
+                    if terms.is_empty() || terms == words {
+                        return;
+                    }
+
+                    let tokenized_term = if terms.len() == 1 {
+                        QueryTerm::Word(terms.remove(0))
+                    } else {
+                        QueryTerm::Phrase(terms)
+                    };
+                    *expression = Expression::Or(vec![
+                        Expression::Term(term.clone()),
+                        Expression::Term(tokenized_term),
+                    ]);
+                }
+                Expression::Or(expressions) => {
+                    for expression in expressions {
+                        tokenize_expression(expression, tokenizer);
+                    }
+                }
+                Expression::Group(clauses) => {
+                    for clause in clauses {
+                        tokenize_expression(&mut clause.expression, tokenizer);
+                    }
+                }
+            }
+        }
+
+        if tokenizer == Tokenizer::Standard {
+            return;
+        }
+
+        for clause in &mut self.clauses {
+            tokenize_expression(&mut clause.expression, tokenizer);
+        }
+    }
+
+    /**
+     * Every word that's searched for, whether on its own or as part of a
+     * phrase, in the order they appear in the query. Words in excluded
+     * clauses aren't searched for, so they're left out.
+     */
+    pub(super) fn words(&self) -> Vec<&str> {
+        fn collect_words<'a>(expression: &'a Expression, words: &mut Vec<&'a str>) {
+            match expression {
+                Expression::Term(QueryTerm::Word(word)) => words.push(word),
+                Expression::Term(QueryTerm::Phrase(phrase)) => {
+                    words.extend(phrase.iter().map(String::as_str));
+                }
+                Expression::Or(expressions) => {
+                    for expression in expressions {
+                        collect_words(expression, words);
+                    }
+                }
+                Expression::Group(clauses) => collect_clause_words(clauses, words),
+            }
+        }
+
+        fn collect_clause_words<'a>(clauses: &'a [Clause], words: &mut Vec<&'a str>) {
+            for clause in clauses {
+                if clause.occurrence != Occurrence::Excluded {
+                    collect_words(&clause.expression, words);
+                }
+            }
+        }
+
+        let mut words = vec![];
+        collect_clause_words(&self.clauses, &mut words);
+        words
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Token {
+    Words(Vec<String>),
+    Phrase(Vec<String>),
+    Plus,
+    Minus,
+    Or,
+    OpenParen,
+    CloseParen,
+    Filter(FieldFilter),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum QueryParseError {
+    UnexpectedToken(Token),
+    UnexpectedEnd,
+    EmptyGroup,
+}
+
+pub(super) fn is_quote(c: char) -> bool {
+    matches!(c, '"' | '\u{201C}' | '\u{201D}')
+}
+
+fn split_into_words(string: &str) -> Vec<String> {
+    string
+        .split([' ', '-'])
+        .filter(|s| !s.is_empty())
+        .map(ToString::to_string)
+        .collect()
+}
+
+/**
+ * Parses a query written in Stork's query language:
+ *
+ * - `+word` requires that every result contains `word`
+ * - `-word` removes results that contain `word`
+ * - `this OR that` matches results containing either term
+ * - parentheses group clauses, e.g. `+(cat OR dog) -fish`
+ * - `"quoted text"` is matched as a phrase
+ * - `key:value` only returns entries whose `key` field is `value`, as long as
+ *   `is_field_key` says that `key` is one of the index's fields. Otherwise
+ *   it's searched for like any other text, so that queries like `std::vec`
+ *   or `10:30` still find something.
+ *
+ * Everything else is matched the way it always has been: every word is
+ * optional, and results are ranked by how many words they match. A query that
+ * can't be parsed (e.g. it has unbalanced parentheses) falls back to that
+ * behavior for the whole query, ignoring any parentheses.
+ */
+pub(super) fn parse(query: &str, is_field_key: impl Fn(&str) -> bool) -> ParsedQuery {
+    let (filter_tokens, tokens): (Vec<Token>, Vec<Token>) = tokenize(query, is_field_key)
+        .into_iter()
+        .partition(|token| matches!(token, Token::Filter(_)));
+
+    let filters = filter_tokens
+        .into_iter()
+        .filter_map(|token| match token {
+            Token::Filter(filter) => Some(filter),
+            _ => None,
+        })
+        .collect();
+
+    let mut position = 0;
+    let clauses = match parse_clauses(&tokens, &mut position, false) {
+        Ok(clauses) => clauses,
+        Err(_) => parse_without_operators(&tokens),
+    };
+
+    ParsedQuery { clauses, filters }
+}
+
+fn parse_without_operators(tokens: &[Token]) -> Vec<Clause> {
+    let mut clauses = vec![];
+    let mut excluded = false;
+
+    for token in tokens {
+        match token {
+            Token::Words(words) if excluded => clauses.push(Clause {
+                occurrence: Occurrence::Excluded,
+                expression: words_expression(words),
+            }),
+            Token::Words(words) => clauses.extend(
+                words
+                    .iter()
+                    .map(|word| Clause::optional_term(QueryTerm::Word(word.clone()))),
+            ),
+            Token::Phrase(words) => clauses.push(Clause {
+                occurrence: if excluded {
+                    Occurrence::Excluded
+                } else {
+                    Occurrence::Optional
+                },
+                expression: Expression::Term(QueryTerm::Phrase(words.clone())),
+            }),
+            Token::Plus
+            | Token::Minus
+            | Token::Or
+            | Token::OpenParen
+            | Token::CloseParen
+            | Token::Filter(_) => {}
+        }
+
+        excluded = token == &Token::Minus;
+    }
+
+    clauses
+}
+
+fn tokenize(query: &str, is_field_key: impl Fn(&str) -> bool) -> Vec<Token> {
+    let chars: Vec<char> = query.chars().collect();
+    let mut tokens: Vec<Token> = vec![];
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        if let Some((filter, end)) =
+            read_field_filter(&chars, i).filter(|(filter, _)| is_field_key(&filter.key))
+        {
+            tokens.push(Token::Filter(filter));
+            i = end;
+            continue;
+        }
+
+        match c {
+            '(' => {
+                tokens.push(Token::OpenParen);
+                i += 1;
+            }
+
+            ')' => {
+                tokens.push(Token::CloseParen);
+                i += 1;
+            }
+
+            // A `+` or `-` is only an operator at the start of a term; otherwise
+            // it's part of a word, and hyphens split words like they always have.
+            '+' | '-' if chars.get(i + 1).is_some_and(|next| !next.is_whitespace()) => {
+                tokens.push(if c == '+' { Token::Plus } else { Token::Minus });
+                i += 1;
+            }
+
+            c if is_quote(c) => {
+                let end = (i + 1..chars.len())
+                    .find(|&j| is_quote(chars[j]))
+                    .unwrap_or(chars.len());
+                let text: String = chars[i + 1..end].iter().collect();
+                let words = split_into_words(&text.to_lowercase());
+                if words.len() > 1 {
+                    tokens.push(Token::Phrase(words));
+                } else if !words.is_empty() {
+                    tokens.push(Token::Words(words));
+                }
+                i = end + 1;
+            }
+
+            _ => {
+                let end = (i..chars.len())
+                    .find(|&j| {
+                        chars[j].is_whitespace()
+                            || matches!(chars[j], '(' | ')')
+                            || is_quote(chars[j])
+                    })
+                    .unwrap_or(chars.len());
+                let text: String = chars[i..end].iter().collect();
+                if text == "OR" {
+                    tokens.push(Token::Or);
+                } else {
+                    let words = split_into_words(&text.to_lowercase());
+                    if !words.is_empty() {
+                        tokens.push(Token::Words(words));
+                    }
+                }
+                i = end;
+            }
+        }
+    }
+
+    tokens
+}
+
+fn is_field_key_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '.')
+}
+
+/**
+ * Reads a `key:value` field filter starting at `start`, if there is one. The
+ * value can be quoted to include spaces. Values that start with `/` or `:`
+ * and keys made only of digits aren't treated as filters, so that URLs like
+ * `https://example.com`, paths like `std::vec`, and times like `10:30` are
+ * still searched for as words.
+ */
+fn read_field_filter(chars: &[char], start: usize) -> Option<(FieldFilter, usize)> {
+    let (excluded, key_start) = match chars[start] {
+        '-' => (true, start + 1),
+        '+' => (false, start + 1),
+        _ => (false, start),
+    };
+
+    let key_end = (key_start..chars.len()).find(|&j| !is_field_key_char(chars[j]))?;
+    let key = &chars[key_start..key_end];
+    if key.is_empty() || key.iter().all(char::is_ascii_digit) || chars[key_end] != ':' {
+        return None;
+    }
+
+    let value_start = key_end + 1;
+    let (value, end): (String, usize) = match chars.get(value_start) {
+        Some(&c) if is_quote(c) => {
+            let value_end = (value_start + 1..chars.len())
+                .find(|&j| is_quote(chars[j]))
+                .unwrap_or(chars.len());
+            (
+                chars[value_start + 1..value_end].iter().collect(),
+                value_end + 1,
+            )
+        }
+        Some(_) => {
+            let value_end = (value_start..chars.len())
+                .find(|&j| chars[j].is_whitespace() || matches!(chars[j], '(' | ')'))
+                .unwrap_or(chars.len());
+            (chars[value_start..value_end].iter().collect(), value_end)
+        }
+        None => return None,
+    };
+
+    let value = value.trim();
+    if value.is_empty() || value.starts_with(['/', ':']) {
+        return None;
+    }
+
+    let filter = FieldFilter {
+        key: key.iter().collect::<String>().to_lowercase(),
+        value: value.to_lowercase(),
+        excluded,
+    };
+
+    Some((filter, end))
+}
+
+fn parse_clauses(
+    tokens: &[Token],
+    position: &mut usize,
+    in_group: bool,
+) -> Result<Vec<Clause>, QueryParseError> {
+    let mut clauses = vec![];
+
+    while let Some(token) = tokens.get(*position) {
+        if token == &Token::CloseParen {
+            if in_group {
+                return Ok(clauses);
+            }
+            return Err(QueryParseError::UnexpectedToken(token.clone()));
+        }
+
+        let occurrence = match token {
+            Token::Plus => Occurrence::Required,
+            Token::Minus => Occurrence::Excluded,
+            _ => Occurrence::Optional,
+        };
+
+        if occurrence != Occurrence::Optional {
+            *position += 1;
+        }
+
+        let expression = parse_or_expression(tokens, position)?;
+        clauses.push(Clause {
+            occurrence,
+            expression,
+        });
+    }
+
+    if in_group {
+        return Err(QueryParseError::UnexpectedEnd);
+    }
+
+    Ok(clauses)
+}
+
+/**
+ * A hyphenated word like `old-world` matches either of its parts, just as it
+ * did before the query language existed.
+ */
+fn words_expression(words: &[String]) -> Expression {
+    if let [word] = words {
+        return Expression::Term(QueryTerm::Word(word.clone()));
+    }
+
+    Expression::Or(
+        words
+            .iter()
+            .map(|word| Expression::Term(QueryTerm::Word(word.clone())))
+            .collect(),
+    )
+}
+
+fn parse_or_expression(
+    tokens: &[Token],
+    position: &mut usize,
+) -> Result<Expression, QueryParseError> {
+    let mut expressions = vec![parse_atom(tokens, position)?];
+
+    while tokens.get(*position) == Some(&Token::Or) {
+        *position += 1;
+        expressions.push(parse_atom(tokens, position)?);
+    }
+
+    if expressions.len() == 1 {
+        return Ok(expressions.remove(0));
+    }
+
+    Ok(Expression::Or(expressions))
+}
+
+fn parse_atom(tokens: &[Token], position: &mut usize) -> Result<Expression, QueryParseError> {
+    let token = tokens
+        .get(*position)
+        .ok_or(QueryParseError::UnexpectedEnd)?;
+    *position += 1;
+
+    match token {
+        Token::Words(words) => Ok(words_expression(words)),
+
+        Token::Phrase(words) => Ok(Expression::Term(QueryTerm::Phrase(words.clone()))),
+
+        Token::OpenParen => {
+            let clauses = parse_clauses(tokens, position, true)?;
+            // parse_clauses only returns successfully inside a group if it
+            // stopped at the closing paren
+            *position += 1;
+            if clauses.is_empty() {
+                return Err(QueryParseError::EmptyGroup);
+            }
+            Ok(Expression::Group(clauses))
+        }
+
+        Token::Plus | Token::Minus | Token::Or | Token::CloseParen | Token::Filter(_) => {
+            Err(QueryParseError::UnexpectedToken(token.clone()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn word(w: &str) -> QueryTerm {
+        QueryTerm::Word(w.to_string())
+    }
+
+    fn phrase(words: &[&str]) -> QueryTerm {
+        QueryTerm::Phrase(words.iter().map(ToString::to_string).collect())
+    }
+
+    fn clause(occurrence: Occurrence, expression: Expression) -> Clause {
+        Clause {
+            occurrence,
+            expression,
+        }
+    }
+
+    fn term(w: &str) -> Expression {
+        Expression::Term(word(w))
+    }
+
+    /**
+     * Parses a query for an index whose entries have `category` and
+     * `section` fields.
+     */
+    fn parse_query(query: &str) -> ParsedQuery {
+        parse(query, |key| ["category", "section"].contains(&key))
+    }
+
+    /**
+     * Splits a query into its terms, without regard for any operators.
+     */
+    fn parse_terms(query: &str) -> Vec<QueryTerm> {
+        parse_without_operators(&tokenize(query, |_| false))
+            .into_iter()
+            .map(|clause| match clause.expression {
+                Expression::Term(term) => term,
+                expression => panic!("expected a term, got {expression:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn unquoted_query_is_split_into_words() {
+        assert_eq!(
+            parse_terms("liber old-world"),
+            vec![word("liber"), word("old"), word("world")]
+        );
+    }
+
+    #[test]
+    fn quoted_query_becomes_phrase() {
+        assert_eq!(
+            parse_terms("the \"federal government\" is"),
+            vec![word("the"), phrase(&["federal", "government"]), word("is")]
+        );
+    }
+
+    #[test]
+    fn curly_quotes_become_phrase() {
+        assert_eq!(
+            parse_terms("\u{201C}federal government\u{201D}"),
+            vec![phrase(&["federal", "government"])]
+        );
+    }
+
+    #[test]
+    fn unterminated_quote_becomes_phrase() {
+        assert_eq!(
+            parse_terms("liberty \"federal govern"),
+            vec![word("liberty"), phrase(&["federal", "govern"])]
+        );
+    }
+
+    #[test]
+    fn single_quoted_word_is_a_word() {
+        assert_eq!(parse_terms("\"liberty\""), vec![word("liberty")]);
+    }
+
+    #[test]
+    fn plain_query_is_all_optional_words() {
+        assert_eq!(
+            parse_query("Liber old world").clauses,
+            vec![
+                clause(Occurrence::Optional, term("liber")),
+                clause(Occurrence::Optional, term("old")),
+                clause(Occurrence::Optional, term("world")),
+            ]
+        );
+    }
+
+    #[test]
+    fn required_and_excluded_terms() {
+        assert_eq!(
+            parse_query("+animate -deprecated \"render loop\"").clauses,
+            vec![
+                clause(Occurrence::Required, term("animate")),
+                clause(Occurrence::Excluded, term("deprecated")),
+                clause(
+                    Occurrence::Optional,
+                    Expression::Term(phrase(&["render", "loop"]))
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn or_binds_adjacent_terms() {
+        assert_eq!(
+            parse_query("cat OR dog fish").clauses,
+            vec![
+                clause(
+                    Occurrence::Optional,
+                    Expression::Or(vec![term("cat"), term("dog")])
+                ),
+                clause(Occurrence::Optional, term("fish")),
+            ]
+        );
+    }
+
+    #[test]
+    fn lowercase_or_is_a_word() {
+        assert_eq!(
+            parse_query("cat or dog").clauses,
+            vec![
+                clause(Occurrence::Optional, term("cat")),
+                clause(Occurrence::Optional, term("or")),
+                clause(Occurrence::Optional, term("dog")),
+            ]
+        );
+    }
+
+    #[test]
+    fn parentheses_group_clauses() {
+        assert_eq!(
+            parse_query("+(cat OR dog) -(fish bird)").clauses,
+            vec![
+                clause(
+                    Occurrence::Required,
+                    Expression::Group(vec![clause(
+                        Occurrence::Optional,
+                        Expression::Or(vec![term("cat"), term("dog")])
+                    )])
+                ),
+                clause(
+                    Occurrence::Excluded,
+                    Expression::Group(vec![
+                        clause(Occurrence::Optional, term("fish")),
+                        clause(Occurrence::Optional, term("bird")),
+                    ])
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn hyphens_inside_words_are_not_operators() {
+        assert_eq!(
+            parse_query("old-world - x").clauses,
+            vec![
+                clause(
+                    Occurrence::Optional,
+                    Expression::Or(vec![term("old"), term("world")])
+                ),
+                clause(Occurrence::Optional, term("x")),
+            ]
+        );
+    }
+
+    #[test]
+    fn query_without_operators_ignores_parentheses_and_or() {
+        assert_eq!(parse_terms("(cat OR +dog"), vec![word("cat"), word("dog")]);
+    }
+
+    #[test]
+    fn unparseable_queries_fall_back_to_plain_terms() {
+        for query in [
+            "(cat dog",
+            "cat) dog",
+            "cat OR dog)",
+            "OR cat dog",
+            "() cat dog",
+            "cat OR OR dog",
+        ] {
+            assert_eq!(
+                parse_query(query).clauses,
+                vec![
+                    clause(Occurrence::Optional, term("cat")),
+                    clause(Occurrence::Optional, term("dog")),
+                ],
+                "query was `{query}`"
+            );
+        }
+    }
+
+    #[test]
+    fn unparseable_queries_keep_exclusions_and_filters() {
+        let parsed = parse_query("(cat -api category:guide -\"big fish\"");
+        assert_eq!(
+            parsed.clauses,
+            vec![
+                clause(Occurrence::Optional, term("cat")),
+                clause(Occurrence::Excluded, term("api")),
+                clause(
+                    Occurrence::Excluded,
+                    Expression::Term(phrase(&["big", "fish"]))
+                ),
+            ]
+        );
+        assert_eq!(
+            parsed.filters,
+            vec![FieldFilter {
+                key: "category".to_string(),
+                value: "guide".to_string(),
+                excluded: false,
+            }]
+        );
+    }
+
+    #[test]
+    fn field_filters_are_separated_from_clauses() {
+        let parsed = parse_query("liberty Category:Guide -section:\"API Reference\"");
+        assert_eq!(
+            parsed.clauses,
+            vec![clause(Occurrence::Optional, term("liberty"))]
+        );
+        assert_eq!(
+            parsed.filters,
+            vec![
+                FieldFilter {
+                    key: "category".to_string(),
+                    value: "guide".to_string(),
+                    excluded: false,
+                },
+                FieldFilter {
+                    key: "section".to_string(),
+                    value: "api reference".to_string(),
+                    excluded: true,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn urls_and_trailing_colons_are_not_field_filters() {
+        let parsed = parse_query("https://example.com category:");
+        assert!(parsed.filters.is_empty());
+        assert_eq!(parsed.clauses.len(), 2);
+    }
+
+    #[test]
+    fn only_the_index_fields_are_field_filters() {
+        let parsed = parse_query("std::vec 10:30 version:2 category::guide");
+        assert!(parsed.filters.is_empty());
+        assert_eq!(
+            parsed.clauses,
+            vec![
+                clause(Occurrence::Optional, term("std::vec")),
+                clause(Occurrence::Optional, term("10:30")),
+                clause(Occurrence::Optional, term("version:2")),
+                clause(Occurrence::Optional, term("category::guide")),
+            ]
+        );
+
+        let parsed = parse("10:30 std:vec", |_| true);
+        assert!(parsed.filters.iter().all(|filter| filter.key == "std"));
+        assert_eq!(
+            parsed.clauses,
+            vec![clause(Occurrence::Optional, term("10:30"))]
+        );
+    }
+
+    #[test]
+    fn parsed_query_lists_every_searched_for_word() {
+        let parsed = parse_query("+(cat OR dog -mouse) -\"big fish\" bird category:pets");
+        assert_eq!(parsed.words(), vec!["cat", "dog", "bird"]);
+    }
+}
@@ -0,0 +1,205 @@
+use std::{cmp::Ordering, collections::BTreeMap};
+
+use crate::{config::FieldType, Result, SortKey, SortOrder};
+
+use super::field_value::FieldValue;
+
+/**
+ * Sorts results that are already ordered by score, then by title, by the
+ * given sort order. The sort is stable, so results that sort the same keep
+ * their score-then-title order. Results without a value for the sort field
+ * come last, whichever direction the results are sorted in.
+ */
+pub(super) fn sort_results(
+    results: &mut [Result],
+    sort_order: &SortOrder,
+    field_types: &BTreeMap<String, FieldType>,
+) {
+    let direction = |ordering: Ordering| {
+        if sort_order.descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    };
+
+    match &sort_order.key {
+        SortKey::Score => results.sort_by(|a, b| direction(a.score.cmp(&b.score))),
+        SortKey::Title => results.sort_by(|a, b| {
+            direction(
+                a.entry
+                    .title
+                    .to_lowercase()
+                    .cmp(&b.entry.title.to_lowercase()),
+            )
+        }),
+        SortKey::Field(key) => {
+            let field_type = field_types.get(key).copied().unwrap_or_default();
+            let field_value = |result: &Result| {
+                result
+                    .entry
+                    .fields
+                    .iter()
+                    .find(|(field_key, _)| field_key.to_lowercase() == *key)
+                    .and_then(|(_, value)| FieldValue::parse(value, field_type))
+            };
+
+            results.sort_by(|a, b| match (field_value(a), field_value(b)) {
+                (Some(a), Some(b)) => direction(a.compare(&b).unwrap_or(Ordering::Equal)),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index_v3::search::search;
+    use crate::index_v3::test_helpers::{build_numbered_index, ranked_titles};
+    use crate::{Entry, SearchOptions};
+    use pretty_assertions::assert_eq;
+    use std::collections::HashMap;
+    use std::convert::TryFrom;
+
+    fn result(title: &str, score: usize, fields: &[(&str, &str)]) -> Result {
+        Result {
+            entry: Entry {
+                url: String::new(),
+                title: title.to_string(),
+                fields: fields
+                    .iter()
+                    .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+                    .collect(),
+            },
+            excerpts: vec![],
+            title_highlight_ranges: vec![],
+            score,
+        }
+    }
+
+    fn sorted_titles(
+        results: &[Result],
+        sort_order: &str,
+        field_types: &[(&str, FieldType)],
+    ) -> Vec<String> {
+        let mut results = results.to_vec();
+        let field_types = field_types
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), *v))
+            .collect();
+        sort_results(
+            &mut results,
+            &SortOrder::try_from(sort_order.to_string()).unwrap(),
+            &field_types,
+        );
+        results
+            .into_iter()
+            .map(|result| result.entry.title)
+            .collect()
+    }
+
+    #[test]
+    fn results_can_be_sorted_newest_first() {
+        let results = vec![
+            result("Undated", 300, &[]),
+            result("Old", 200, &[("Date", "2021-06-01")]),
+            result("New", 100, &[("Date", "2024-01-15")]),
+            result("Also new", 50, &[("Date", "2024-01-15T00:00:00Z")]),
+        ];
+
+        assert_eq!(
+            sorted_titles(&results, "date desc", &[("date", FieldType::Date)]),
+            vec!["New", "Also new", "Old", "Undated"]
+        );
+        assert_eq!(
+            sorted_titles(&results, "date asc", &[("date", FieldType::Date)]),
+            vec!["Old", "New", "Also new", "Undated"]
+        );
+    }
+
+    #[test]
+    fn numbers_sort_numerically_only_when_declared() {
+        let results = vec![
+            result("Nine", 0, &[("weight", "9")]),
+            result("Ten", 0, &[("weight", "10")]),
+        ];
+
+        assert_eq!(
+            sorted_titles(&results, "weight", &[("weight", FieldType::Number)]),
+            vec!["Nine", "Ten"]
+        );
+        assert_eq!(sorted_titles(&results, "weight", &[]), vec!["Ten", "Nine"]);
+    }
+
+    #[test]
+    fn results_can_be_sorted_by_score_or_title() {
+        let results = vec![
+            result("b", 2, &[]),
+            result("A", 1, &[]),
+            result("c", 3, &[]),
+        ];
+
+        assert_eq!(sorted_titles(&results, "score", &[]), vec!["c", "b", "A"]);
+        assert_eq!(
+            sorted_titles(&results, "score asc", &[]),
+            vec!["A", "b", "c"]
+        );
+        assert_eq!(sorted_titles(&results, "title", &[]), vec!["A", "b", "c"]);
+        assert_eq!(
+            sorted_titles(&results, "title desc", &[]),
+            vec!["c", "b", "A"]
+        );
+    }
+
+    #[test]
+    fn results_can_be_filtered_and_sorted_by_typed_fields() {
+        let index = build_numbered_index(&["A post about birds"; 3], |config| {
+            let fields = [
+                ("2021-06-01", "3"),
+                ("2024-01-15", "1"),
+                ("2023-09-30", "2"),
+            ];
+            for (file, (date, weight)) in config.input.files.iter_mut().zip(fields) {
+                file.fields = HashMap::from([
+                    ("date".to_string(), date.to_string()),
+                    ("weight".to_string(), weight.to_string()),
+                ]);
+            }
+            config.output.field_types = BTreeMap::from([
+                ("Date".to_string(), FieldType::Date),
+                ("weight".to_string(), FieldType::Number),
+            ]);
+        });
+
+        let titles = |options: &str| {
+            let options: SearchOptions = serde_json::from_str(options).unwrap();
+            ranked_titles(&search(&index, "birds", &options))
+                .into_iter()
+                .map(ToString::to_string)
+                .collect::<Vec<String>>()
+        };
+
+        assert_eq!(
+            titles(r#"{"sort": "date desc"}"#),
+            vec!["Document 1", "Document 2", "Document 0"]
+        );
+        assert_eq!(
+            titles(r#"{"sort": "weight"}"#),
+            vec!["Document 1", "Document 2", "Document 0"]
+        );
+        assert_eq!(
+            titles(r#"{"range_filters": ["date >= 2023-01-01"], "sort": "date"}"#),
+            vec!["Document 2", "Document 1"]
+        );
+
+        let output = search(
+            &index,
+            "birds",
+            &serde_json::from_str(r#"{"range_filters": ["weight > 1"]}"#).unwrap(),
+        );
+        assert_eq!(output.total_hit_count, 2);
+    }
+}
@@ -0,0 +1,47 @@
+use rust_stemmers::Stemmer;
+
+use crate::V3Index as Index;
+
+use super::super::scores::STEM_SCORE;
+use super::get_container_intermediate_excerpts;
+use super::intermediate_excerpt::IntermediateExcerpt;
+
+/**
+ * Finds the words in the index that share a stem with the query word, using
+ * the index's stemming algorithm, and returns intermediate excerpts for each
+ * of them.
+ *
+ * The index only has reverse-stem aliases for words that appear in the
+ * corpus, so this lets a query like `governing` find `governed` even when
+ * `governing` itself was never indexed.
+ */
+pub(super) fn get_intermediate_excerpts(index: &Index, word: &str) -> Vec<IntermediateExcerpt> {
+    let Some(stem_algorithm) = index.config.stem_algorithm else {
+        return vec![];
+    };
+
+    let stemmer = Stemmer::create(stem_algorithm);
+    let stem = stemmer.stem(word);
+
+    // Words that share a stem almost always start with it, except that
+    // stemming can change the stem's last letter (`happy` becomes `happi`).
+    // Only the words that start with the rest of the stem need to be stemmed.
+    let stem_chars: Vec<char> = stem.chars().collect();
+    let prefix: String = if stem_chars.len() > 3 {
+        stem_chars[..stem_chars.len() - 1].iter().collect()
+    } else {
+        stem.to_string()
+    };
+
+    index
+        .containers
+        .range(prefix.clone()..)
+        .take_while(|(key, _)| key.starts_with(&prefix))
+        .filter(|(key, container)| {
+            !container.results.is_empty() && key.as_str() != word && stemmer.stem(key) == stem
+        })
+        .flat_map(|(key, container)| {
+            get_container_intermediate_excerpts(key, container, STEM_SCORE)
+        })
+        .collect()
+}
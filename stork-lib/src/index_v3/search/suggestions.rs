@@ -0,0 +1,170 @@
+use std::collections::HashMap;
+
+use crate::{index_v3::normalization::normalize, V3Index as Index};
+
+use super::fuzzy::{edit_distance, length_is_within_edit_distance, max_edit_distance};
+use super::is_stopword;
+use super::query::{is_quote, ParsedQuery};
+
+/// Queries with more hits than this don't get suggestions.
+pub(super) const MAX_HIT_COUNT_FOR_SUGGESTIONS: usize = 2;
+
+const MAX_SUGGESTIONS: usize = 3;
+
+#[derive(Debug)]
+struct Correction {
+    word: String,
+    distance: usize,
+    entry_count: usize,
+}
+
+/**
+ * Builds corrected versions of a query by swapping each word that isn't in
+ * the index (or that's much rarer than a similarly-spelled word) for the
+ * closest, most common word in the index's vocabulary.
+ */
+pub(super) fn get_suggestions(
+    index: &Index,
+    query: &str,
+    parsed_query: &ParsedQuery,
+) -> Vec<String> {
+    let mut corrections: HashMap<&str, Vec<Correction>> = HashMap::new();
+
+    for word in parsed_query.words() {
+        if corrections.contains_key(word) || is_stopword(index, word) {
+            continue;
+        }
+
+        let word_corrections = get_corrections(index, word);
+        if !word_corrections.is_empty() {
+            corrections.insert(word, word_corrections);
+        }
+    }
+
+    if corrections.is_empty() {
+        return vec![];
+    }
+
+    let mut suggestions: Vec<String> = vec![];
+    for rank in 0..MAX_SUGGESTIONS {
+        let suggestion = replace_words(query, |word| {
+            corrections
+                .get(normalize(word, index.config.normalization).as_str())
+                .map(|word_corrections| {
+                    word_corrections[rank.min(word_corrections.len() - 1)]
+                        .word
+                        .as_str()
+                })
+        });
+
+        if !suggestions.contains(&suggestion) {
+            suggestions.push(suggestion);
+        }
+    }
+
+    suggestions
+}
+
+fn get_corrections(index: &Index, word: &str) -> Vec<Correction> {
+    let word_chars: Vec<char> = word.chars().collect();
+    let max_distance = max_edit_distance(word_chars.len());
+
+    if max_distance == 0 {
+        return vec![];
+    }
+
+    let own_entry_count = index
+        .containers
+        .get(word)
+        .map_or(0, |container| container.results.len());
+
+    // A word that's only a prefix of other words isn't misspelled; the
+    // user is probably still typing it.
+    if own_entry_count == 0 && index.containers.contains_key(word) {
+        return vec![];
+    }
+
+    let mut corrections: Vec<Correction> = index
+        .containers
+        .iter()
+        .filter(|(key, container)| {
+            container.results.len() > own_entry_count
+                && key.as_str() != word
+                && length_is_within_edit_distance(key, word_chars.len(), max_distance)
+        })
+        .filter_map(|(key, container)| {
+            let key_chars: Vec<char> = key.chars().collect();
+            edit_distance(&word_chars, &key_chars, max_distance).map(|distance| Correction {
+                word: key.clone(),
+                distance,
+                entry_count: container.results.len(),
+            })
+        })
+        .collect();
+
+    corrections.sort_by(|a, b| {
+        a.distance
+            .cmp(&b.distance)
+            .then(b.entry_count.cmp(&a.entry_count))
+            .then(a.word.cmp(&b.word))
+    });
+    corrections.truncate(MAX_SUGGESTIONS);
+    corrections
+}
+
+/**
+ * Replaces words in a query while leaving its operators, quotes, and field
+ * filters as they were written. `replacement_for` is given each word as it
+ * was written, too.
+ */
+fn replace_words<'a, F>(query: &str, replacement_for: F) -> String
+where
+    F: Fn(&str) -> Option<&'a str>,
+{
+    fn is_word_char(c: char) -> bool {
+        !c.is_whitespace() && !matches!(c, '+' | '-' | '(' | ')' | ':') && !is_quote(c)
+    }
+
+    let chars: Vec<char> = query.chars().collect();
+    let mut output = String::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        if !is_word_char(chars[i]) {
+            output.push(chars[i]);
+            i += 1;
+            continue;
+        }
+
+        let end = (i..chars.len())
+            .find(|&j| !is_word_char(chars[j]))
+            .unwrap_or(chars.len());
+        let word: String = chars[i..end].iter().collect();
+
+        // A field filter's key and value are left alone, including a
+        // quoted value's spaces
+        if chars.get(end) == Some(&':') {
+            let value_end = if chars.get(end + 1).is_some_and(|c| is_quote(*c)) {
+                (end + 2..chars.len())
+                    .find(|&j| is_quote(chars[j]))
+                    .map_or(chars.len(), |j| j + 1)
+            } else {
+                (end + 1..chars.len())
+                    .find(|&j| chars[j].is_whitespace())
+                    .unwrap_or(chars.len())
+            };
+            output.extend(&chars[i..value_end]);
+            i = value_end;
+            continue;
+        }
+
+        if word == "OR" {
+            output.push_str(&word);
+        } else {
+            output.push_str(replacement_for(&word).unwrap_or(&word));
+        }
+        i = end;
+    }
+
+    output
+}
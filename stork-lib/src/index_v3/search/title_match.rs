@@ -0,0 +1,377 @@
+use std::collections::BTreeSet;
+
+use crate::{
+    config::{Normalization, TitleBoost, Tokenizer},
+    index_v3::{
+        normalization::normalize,
+        tokenizer::{char_is_cjk, split_into_word_ranges},
+    },
+    HighlightRange, V3Index as Index,
+};
+
+use super::is_stopword;
+
+/// A title that starts with the query earns this many extra title words' worth of score.
+const PREFIX_MATCH_MULTIPLIER: usize = 2;
+
+/// A title that is exactly the query earns this many extra title words' worth of score.
+const EXACT_MATCH_MULTIPLIER: usize = 4;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(super) enum TitleMatchKind {
+    None,
+
+    /// Some of the title's words were matched.
+    Partial,
+
+    /// The title starts with the query. The query's last word only has to be
+    /// the start of a title word, since the user might still be typing it,
+    /// as long as it can match the start of title words.
+    Prefix,
+
+    /// The title's words are exactly the query's words.
+    Exact,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) struct TitleMatch {
+    pub(super) kind: TitleMatchKind,
+    pub(super) highlight_ranges: Vec<HighlightRange>,
+    pub(super) score: usize,
+}
+
+struct TitleWord {
+    normalized: String,
+    range: HighlightRange,
+}
+
+/**
+ * Scores how well an entry's title matches the query.
+ *
+ * A title word is matched if the index matched it (`matched_word_indices`,
+ * from the entry's title excerpts), if it's one of the query's words, or if
+ * it starts with the query's last word and `last_word_is_prefix` is set.
+ * Each matched word is worth the
+ * configured `TitleBoost`'s per-word score, and prefix and exact title
+ * matches earn a further bonus, so that with identical contents an exact
+ * title match always outranks a prefix match, which always outranks a
+ * partial match.
+ *
+ * Since this works from the entry's title rather than from its excerpts, it
+ * also works for indexes built with `excerpts_per_result = 0`.
+ */
+pub(super) fn get_title_match(
+    title: &str,
+    query_words: &[String],
+    last_word_is_prefix: bool,
+    matched_word_indices: &BTreeSet<usize>,
+    title_boost: &TitleBoost,
+    normalization: Normalization,
+    tokenizer: Tokenizer,
+) -> TitleMatch {
+    let title_words = split_title(title, normalization, tokenizer);
+    let query_words: Vec<String> = query_words
+        .iter()
+        .map(|word| normalize_title_word(word, normalization))
+        .filter(|word| !word.is_empty())
+        .collect();
+    let last_query_word = query_words.last();
+    let starts_with_last_query_word = |title_word: &TitleWord| {
+        last_query_word.is_some_and(|last| {
+            title_word.normalized == *last
+                || (last_word_is_prefix && title_word.normalized.starts_with(last))
+        })
+    };
+
+    let matched_words: BTreeSet<usize> = title_words
+        .iter()
+        .enumerate()
+        .filter(|(index, title_word)| {
+            matched_word_indices.contains(index)
+                || query_words.contains(&title_word.normalized)
+                || starts_with_last_query_word(title_word)
+        })
+        .map(|(index, _)| index)
+        .collect();
+
+    let is_prefix_match = match query_words.split_last() {
+        Some((_, rest)) if query_words.len() <= title_words.len() => {
+            rest.iter()
+                .zip(&title_words)
+                .all(|(query_word, title_word)| *query_word == title_word.normalized)
+                && starts_with_last_query_word(&title_words[rest.len()])
+        }
+        _ => false,
+    };
+
+    let kind = if is_prefix_match
+        && query_words.len() == title_words.len()
+        && last_query_word == title_words.last().map(|word| &word.normalized)
+    {
+        TitleMatchKind::Exact
+    } else if is_prefix_match {
+        TitleMatchKind::Prefix
+    } else if matched_words.is_empty() {
+        TitleMatchKind::None
+    } else {
+        TitleMatchKind::Partial
+    };
+
+    let word_score = title_word_score(title_boost);
+    let bonus = match kind {
+        TitleMatchKind::Exact => EXACT_MATCH_MULTIPLIER,
+        TitleMatchKind::Prefix => PREFIX_MATCH_MULTIPLIER,
+        TitleMatchKind::Partial | TitleMatchKind::None => 0,
+    };
+
+    TitleMatch {
+        kind,
+        highlight_ranges: matched_words
+            .iter()
+            .map(|index| title_words[*index].range.clone())
+            .collect(),
+        score: (matched_words.len() + bonus) * word_score,
+    }
+}
+
+/**
+ * Whether the last query word can match the start of title words. Like the
+ * prefixes the index has aliases for, it has to be at least the minimum
+ * indexed substring length, and it can't be a stopword.
+ */
+pub(super) fn can_match_title_prefixes(index: &Index, word: &str) -> bool {
+    let chars: Vec<char> = word.chars().collect();
+    let minimum_length = if chars.iter().all(|c| char_is_cjk(*c)) {
+        index.config.minimum_index_ideographic_substring_length
+    } else {
+        index.config.minimum_indexed_substring_length
+    };
+
+    !chars.is_empty() && chars.len() >= usize::from(minimum_length) && !is_stopword(index, word)
+}
+
+fn title_word_score(title_boost: &TitleBoost) -> usize {
+    match title_boost {
+        TitleBoost::Minimal => 25,
+        TitleBoost::Moderate => 75,
+        TitleBoost::Large => 150,
+        TitleBoost::Ridiculous => 5000,
+    }
+}
+
+fn normalize_title_word(word: &str, normalization: Normalization) -> String {
+    normalize(word, normalization)
+        .trim_matches(|c: char| c.is_ascii_punctuation())
+        .to_string()
+}
+
+/**
+ * Splits a title into words the same way the title was split when the index
+ * was built, so that word indices line up with the title's excerpts.
+ * Highlight ranges are measured in characters.
+ */
+fn split_title(title: &str, normalization: Normalization, tokenizer: Tokenizer) -> Vec<TitleWord> {
+    let chars: Vec<char> = title.chars().collect();
+
+    // Titles are split on hyphens too, like they are when they're indexed
+    split_into_word_ranges(&title.replace('-', " "), tokenizer)
+        .into_iter()
+        .map(|range| TitleWord {
+            normalized: normalize_title_word(
+                &chars[range.clone()].iter().collect::<String>(),
+                normalization,
+            ),
+            range: HighlightRange {
+                beginning: range.start,
+                end: range.end,
+            },
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index_v3::search::search;
+    use crate::index_v3::test_helpers::{build_index, ranked_titles};
+    use crate::SearchOptions;
+    use pretty_assertions::assert_eq;
+
+    fn words(query: &str) -> Vec<String> {
+        query.split_whitespace().map(ToString::to_string).collect()
+    }
+
+    fn kind(title: &str, query: &str) -> TitleMatchKind {
+        get_title_match(
+            title,
+            &words(query),
+            true,
+            &BTreeSet::new(),
+            &TitleBoost::default(),
+            Normalization::Nfkc,
+            Tokenizer::Standard,
+        )
+        .kind
+    }
+
+    #[test]
+    fn title_match_kinds() {
+        assert_eq!(
+            kind("Getting Started", "getting started"),
+            TitleMatchKind::Exact
+        );
+        assert_eq!(
+            kind("Getting Started!", "Getting Started"),
+            TitleMatchKind::Exact
+        );
+        assert_eq!(
+            kind("Getting Started", "getting sta"),
+            TitleMatchKind::Prefix
+        );
+        assert_eq!(kind("Getting Started", "getting"), TitleMatchKind::Prefix);
+        assert_eq!(kind("Getting Started", "started"), TitleMatchKind::Partial);
+        assert_eq!(
+            kind("Getting Started", "started getting"),
+            TitleMatchKind::Partial
+        );
+        assert_eq!(
+            kind("Getting Started", "configuration"),
+            TitleMatchKind::None
+        );
+        assert_eq!(kind("Getting Started", ""), TitleMatchKind::None);
+    }
+
+    #[test]
+    fn title_highlight_ranges_are_character_offsets() {
+        let title_match = get_title_match(
+            "Café -- au lait",
+            &words("lait"),
+            true,
+            &BTreeSet::from([0]),
+            &TitleBoost::Minimal,
+            Normalization::Nfkc,
+            Tokenizer::Standard,
+        );
+
+        assert_eq!(
+            title_match.highlight_ranges,
+            vec![
+                HighlightRange {
+                    beginning: 0,
+                    end: 4
+                },
+                HighlightRange {
+                    beginning: 11,
+                    end: 15
+                }
+            ]
+        );
+        assert_eq!(title_match.score, 50);
+    }
+
+    #[test]
+    fn short_and_stopword_last_query_words_only_match_whole_title_words() {
+        let index = build_index(
+            &[
+                ("Federalist Papers", "Essays"),
+                ("The Federalist", "Essays"),
+            ],
+            |_| {},
+        );
+        let title_ranges = |query: &str| {
+            let mut output = search(&index, query, &SearchOptions::default());
+            output
+                .results
+                .sort_by(|a, b| a.entry.title.cmp(&b.entry.title));
+            output
+                .results
+                .into_iter()
+                .map(|result| result.title_highlight_ranges)
+                .collect::<Vec<Vec<HighlightRange>>>()
+        };
+        let range = |beginning, end| HighlightRange { beginning, end };
+
+        assert_eq!(
+            title_ranges("federalist p"),
+            vec![vec![range(0, 10)], vec![range(4, 14)]]
+        );
+        assert_eq!(
+            title_ranges("federalist pap"),
+            vec![vec![range(0, 10), range(11, 17)], vec![range(4, 14)]]
+        );
+        assert_eq!(
+            title_ranges("federalist th"),
+            vec![vec![range(0, 10)], vec![range(4, 14)]]
+        );
+    }
+
+    fn build_title_test_index(
+        titles: &[&str],
+        title_boost: &TitleBoost,
+        excerpts_per_result: u8,
+    ) -> Index {
+        let documents: Vec<(&str, &str)> = titles
+            .iter()
+            .map(|title| (*title, "Read this first: getting started is easy."))
+            .collect();
+
+        build_index(&documents, |config| {
+            config.input.title_boost = title_boost.clone();
+            config.output.excerpts_per_result = excerpts_per_result;
+        })
+    }
+
+    #[test]
+    fn title_matches_rank_exact_then_prefix_then_partial() {
+        let titles = [
+            "Reference",
+            "Before Getting Started",
+            "Getting Started with Plugins",
+            "Getting Started",
+        ];
+
+        for title_boost in [
+            TitleBoost::Minimal,
+            TitleBoost::Moderate,
+            TitleBoost::Large,
+            TitleBoost::Ridiculous,
+        ] {
+            for excerpts_per_result in [0, 5] {
+                let index = build_title_test_index(&titles, &title_boost, excerpts_per_result);
+                let output = search(&index, "getting started", &SearchOptions::default());
+
+                assert_eq!(
+                    ranked_titles(&output),
+                    vec![
+                        "Getting Started",
+                        "Getting Started with Plugins",
+                        "Before Getting Started",
+                        "Reference"
+                    ],
+                    "{title_boost:?} with {excerpts_per_result} excerpts per result"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn title_only_matches_are_found_without_excerpts() {
+        let index = build_title_test_index(&["Installation", "Reference"], &TitleBoost::Large, 0);
+        let output = search(&index, "install", &SearchOptions::default());
+
+        assert_eq!(output.total_hit_count, 1);
+        let result = &output.results[0];
+        assert_eq!(result.entry.title, "Installation");
+        assert!(result.excerpts.is_empty());
+        assert_eq!(
+            result.title_highlight_ranges,
+            vec![HighlightRange {
+                beginning: 0,
+                end: 12
+            }]
+        );
+        // Without excerpts, a prefix match scores like the word it matched,
+        // plus one matched title word and the prefix match bonus
+        assert_eq!(result.score, 128 + (1 + 2) * 150);
+    }
+}
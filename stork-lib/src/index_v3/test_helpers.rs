@@ -0,0 +1,71 @@
+use crate::config::{Config, DataSource, File, Filetype};
+use crate::{Output, V3Index as Index};
+
+/**
+ * Builds an index with a plain text file for each `(title, contents)` pair.
+ * `configure` can change the config before the index is built, including the
+ * files, which are in the same order as `documents`.
+ */
+pub(super) fn build_index(
+    documents: &[(&str, &str)],
+    configure: impl FnOnce(&mut Config),
+) -> Index {
+    let mut config = Config::default();
+    config.input.files = documents
+        .iter()
+        .map(|(title, contents)| File {
+            title: (*title).to_string(),
+            explicit_source: Some(DataSource::Contents((*contents).to_string())),
+            filetype: Some(Filetype::PlainText),
+            ..File::default()
+        })
+        .collect();
+
+    configure(&mut config);
+    super::build(&config).unwrap().index
+}
+
+/**
+ * Builds an index like `build_index` does, titling the documents `Document 0`,
+ * `Document 1`, and so on.
+ */
+pub(super) fn build_numbered_index(
+    contents: &[&str],
+    configure: impl FnOnce(&mut Config),
+) -> Index {
+    let titles: Vec<String> = (0..contents.len())
+        .map(|i| format!("Document {i}"))
+        .collect();
+    let documents: Vec<(&str, &str)> = titles
+        .iter()
+        .map(String::as_str)
+        .zip(contents.iter().copied())
+        .collect();
+
+    build_index(&documents, configure)
+}
+
+/**
+ * The titles of a search's results, sorted, for checking which entries
+ * matched regardless of how they were ranked.
+ */
+pub(super) fn result_titles(output: Output) -> Vec<String> {
+    let mut titles: Vec<String> = output
+        .results
+        .into_iter()
+        .map(|result| result.entry.title)
+        .collect();
+    titles.sort();
+    titles
+}
+
+/**
+ * The titles of a search's results, in the order they were ranked.
+ */
+pub(super) fn ranked_titles(output: &Output) -> Vec<&str> {
+    output
+        .results
+        .iter()
+        .map(|result| result.entry.title.as_str())
+        .collect()
+}
@@ -0,0 +1,310 @@
+use std::ops::Range;
+
+use crate::config::Tokenizer;
+
+use super::AnnotatedWord;
+
+/**
+ * Splits text into words, returning the range of characters each word
+ * covers in the text.
+ *
+ * Every tokenizer splits on whitespace, like `str::split_whitespace` does.
+ * `Tokenizer::CJKBigram` also gives each Chinese, Japanese, or Korean
+ * character its own word, since those languages usually aren't written with
+ * spaces between words.
+ */
+pub(super) fn split_into_word_ranges(text: &str, tokenizer: Tokenizer) -> Vec<Range<usize>> {
+    let mut ranges = vec![];
+    let mut beginning: Option<usize> = None;
+
+    for (index, c) in text.chars().chain(std::iter::once(' ')).enumerate() {
+        let is_separator = c.is_whitespace();
+        let is_own_word = tokenizer == Tokenizer::CJKBigram && char_is_cjk(c);
+
+        if is_separator || is_own_word {
+            if let Some(beginning) = beginning.take() {
+                ranges.push(beginning..index);
+            }
+        }
+
+        if is_own_word {
+            ranges.push(index..index + 1);
+        } else if !is_separator && beginning.is_none() {
+            beginning = Some(index);
+        }
+    }
+
+    ranges
+}
+
+fn split_into_words(text: &str, tokenizer: Tokenizer) -> Vec<String> {
+    let chars: Vec<char> = text.chars().collect();
+    split_into_word_ranges(text, tokenizer)
+        .into_iter()
+        .map(|range| chars[range].iter().collect())
+        .collect()
+}
+
+/**
+ * Splits text into words like `split_into_word_ranges` does, noting which
+ * words came right after the previous word so that `join_words` can put the
+ * text back together with its spacing.
+ */
+pub(super) fn split_into_annotated_words(text: &str, tokenizer: Tokenizer) -> Vec<AnnotatedWord> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut previous_end = None;
+
+    split_into_word_ranges(text, tokenizer)
+        .into_iter()
+        .map(|range| {
+            let joins_previous_word = previous_end == Some(range.start);
+            previous_end = Some(range.end);
+            AnnotatedWord {
+                word: chars[range].iter().collect(),
+                joins_previous_word,
+                ..AnnotatedWord::default()
+            }
+        })
+        .collect()
+}
+
+/**
+ * Joins words back into text, with a space before each word that doesn't
+ * join the previous word.
+ */
+pub(super) fn join_words(words: &[AnnotatedWord]) -> String {
+    let mut text = String::new();
+    for (index, annotated_word) in words.iter().enumerate() {
+        if index > 0 && !annotated_word.joins_previous_word {
+            text.push(' ');
+        }
+        text.push_str(&annotated_word.word);
+    }
+    text
+}
+
+/**
+ * Given the (normalized) words of a document, returns the term each word
+ * should be indexed under.
+ *
+ * With `Tokenizer::CJKBigram`, each CJK character is indexed along with the
+ * character after it, so that searching for a CJK word matches the pairs of
+ * characters that make it up, without needing a dictionary of words. The
+ * last character of a run is indexed on its own.
+ */
+pub(super) fn index_terms(words: &[String], tokenizer: Tokenizer) -> Vec<String> {
+    match tokenizer {
+        Tokenizer::Standard => words.to_vec(),
+        Tokenizer::CJKBigram => words
+            .iter()
+            .enumerate()
+            .map(|(index, word)| match words.get(index + 1) {
+                Some(next_word) if is_cjk_character(word) && is_cjk_character(next_word) => {
+                    format!("{word}{next_word}")
+                }
+                _ => word.clone(),
+            })
+            .collect(),
+    }
+}
+
+/**
+ * Splits a (normalized) query word into the terms that have to be found next
+ * to each other in the index for the word to match.
+ */
+pub(super) fn query_terms(word: &str, tokenizer: Tokenizer) -> Vec<String> {
+    let words = split_into_words(word, tokenizer);
+    let mut terms = index_terms(&words, tokenizer);
+
+    // The run's last character was only indexed on its own at the end of a
+    // run in the document, but as part of the previous term it's already
+    // matched wherever it follows the rest of the query.
+    if terms.len() > 1 && words.last().is_some_and(|last| is_cjk_character(last)) {
+        terms.pop();
+    }
+
+    terms
+}
+
+fn is_cjk_character(word: &str) -> bool {
+    let mut chars = word.chars();
+    matches!((chars.next(), chars.next()), (Some(c), None) if char_is_cjk(c))
+}
+
+pub(super) fn char_is_cjk(c: char) -> bool {
+    char_is_cjk_ideograph(c)
+        || matches!(c,
+            // Hiragana and Katakana
+            '\u{3040}'..='\u{30FF}' |
+            // Hangul Syllables
+            '\u{AC00}'..='\u{D7AF}'
+        )
+}
+
+pub(super) fn char_is_cjk_ideograph(c: char) -> bool {
+    // Block ranges sourced from https://en.wikipedia.org/wiki/CJK_Unified_Ideographs#CJK_Unified_Ideographs_blocks
+    matches!(c,
+        // CJK Unified Ideographs
+        '\u{4E00}'..='\u{62FF}' |
+        '\u{6300}'..='\u{77FF}' |
+        '\u{7800}'..='\u{8CFF}' |
+        '\u{8D00}'..='\u{9FFF}' |
+        // CJK Unified Ideographs Extension A
+        '\u{3400}'..='\u{4DBF}' |
+        // CJK Unified Ideographs Extension B
+        '\u{20000}'..='\u{215FF}' |
+        '\u{21600}'..='\u{230FF}' |
+        '\u{23100}'..='\u{245FF}' |
+        '\u{24600}'..='\u{260FF}' |
+        '\u{26100}'..='\u{275FF}' |
+        '\u{27600}'..='\u{290FF}' |
+        '\u{29100}'..='\u{2A6DF}' |
+        // CJK Unified Ideographs Extension C
+        '\u{2A700}'..='\u{2B73F}' |
+        // CJK Unified Ideographs Extension D
+        '\u{2B740}'..='\u{2B81F}' |
+        // CJK Unified Ideographs Extension E
+        '\u{2B820}'..='\u{2CEAF}' |
+        // CJK Unified Ideographs Extension F
+        '\u{2CEB0}'..='\u{2EBEF}' |
+        // CJK Unified Ideographs Extension G
+        '\u{30000}'..='\u{3134F}' |
+        // CJK Compatibility Ideographs
+        '\u{F900}'..='\u{FAFF}'
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index_v3::search::search;
+    use crate::index_v3::test_helpers::{build_numbered_index, result_titles};
+    use crate::{HighlightRange, SearchOptions};
+    use pretty_assertions::assert_eq;
+
+    fn strings(words: &[&str]) -> Vec<String> {
+        words.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn standard_tokenizer_splits_on_whitespace() {
+        assert_eq!(
+            split_into_words(
+                "Hastings-on-hudson  is\u{a0}nice 東京都",
+                Tokenizer::Standard
+            ),
+            strings(&["Hastings-on-hudson", "is", "nice", "東京都"])
+        );
+    }
+
+    #[test]
+    fn cjk_tokenizer_gives_each_cjk_character_its_own_word() {
+        let text = "Stork は東京で";
+        assert_eq!(
+            split_into_words(text, Tokenizer::CJKBigram),
+            strings(&["Stork", "は", "東", "京", "で"])
+        );
+        assert_eq!(
+            split_into_word_ranges(text, Tokenizer::CJKBigram),
+            vec![0..5, 6..7, 7..8, 8..9, 9..10]
+        );
+    }
+
+    #[test]
+    fn cjk_characters_are_indexed_as_bigrams() {
+        let words = strings(&["stork", "東", "京", "都", "と", "search"]);
+        assert_eq!(
+            index_terms(&words, Tokenizer::CJKBigram),
+            strings(&["stork", "東京", "京都", "都と", "と", "search"])
+        );
+        assert_eq!(index_terms(&words, Tokenizer::Standard), words);
+    }
+
+    #[test]
+    fn cjk_query_words_become_consecutive_bigrams() {
+        assert_eq!(
+            query_terms("東京都", Tokenizer::CJKBigram),
+            strings(&["東京", "京都"])
+        );
+        assert_eq!(query_terms("東", Tokenizer::CJKBigram), strings(&["東"]));
+        assert_eq!(
+            query_terms("東京都", Tokenizer::Standard),
+            strings(&["東京都"])
+        );
+    }
+
+    #[test]
+    fn cjk_words_note_when_they_follow_the_previous_word() {
+        let words: Vec<(String, bool)> =
+            split_into_annotated_words("Stork は東京", Tokenizer::CJKBigram)
+                .into_iter()
+                .map(|annotated_word| (annotated_word.word, annotated_word.joins_previous_word))
+                .collect();
+        assert_eq!(
+            words,
+            vec![
+                ("Stork".to_string(), false),
+                ("は".to_string(), false),
+                ("東".to_string(), true),
+                ("京".to_string(), true),
+            ]
+        );
+    }
+
+    #[test]
+    fn joined_words_keep_their_spacing() {
+        let text = "Stork は東京で  search";
+        assert_eq!(
+            join_words(&split_into_annotated_words(text, Tokenizer::CJKBigram)),
+            "Stork は東京で search"
+        );
+        assert_eq!(
+            join_words(&split_into_annotated_words(text, Tokenizer::Standard)),
+            "Stork は東京で search"
+        );
+    }
+
+    #[test]
+    fn cjk_text_is_searchable_with_the_bigram_tokenizer() {
+        let index = build_numbered_index(
+            &[
+                "東京都に住んでいます。",
+                "京都は美しい。",
+                "Stork is a search library.",
+                // Split with the standard tokenizer, so it's indexed whole
+                "美しい。",
+            ],
+            |config| {
+                for file in &mut config.input.files[..2] {
+                    file.tokenizer_override = Some(Tokenizer::CJKBigram);
+                }
+            },
+        );
+
+        let titles = |query: &str| result_titles(search(&index, query, &SearchOptions::default()));
+        assert_eq!(titles("東京"), vec!["Document 0"]);
+        assert_eq!(titles("東京都"), vec!["Document 0"]);
+        assert_eq!(titles("京都"), vec!["Document 0", "Document 1"]);
+        assert_eq!(titles("美しい"), vec!["Document 1", "Document 3"]);
+        assert_eq!(titles("美"), vec!["Document 1"]);
+        assert_eq!(titles("大阪"), Vec::<String>::new());
+        assert_eq!(titles("library"), vec!["Document 2"]);
+
+        let output = search(&index, "住んで", &SearchOptions::default());
+        let excerpt = &output.results[0].excerpts[0];
+        assert_eq!(excerpt.text, "東京都に住んでいます。");
+        assert_eq!(
+            excerpt.highlight_ranges,
+            vec![
+                HighlightRange {
+                    beginning: 4,
+                    end: 5
+                },
+                HighlightRange {
+                    beginning: 5,
+                    end: 6
+                }
+            ]
+        );
+    }
+}
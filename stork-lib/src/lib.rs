@@ -21,12 +21,15 @@ pub type Fields = HashMap<String, String>;
 
 mod output;
 pub use output::{
-    Entry, Excerpt, HighlightRange, IndexMetadata, InternalWordAnnotation, Output, Result,
+    Entry, Excerpt, Facets, HighlightRange, IndexMetadata, InternalWordAnnotation, Output, Result,
 };
 
 mod input;
 use input::{IndexVersioningError, VersionedIndex};
 
+mod search_options;
+pub use search_options::{Comparison, RangeFilter, SearchOptions, SortKey, SortOrder};
+
 mod stopwords;
 use stopwords::STOPWORDS as stopwords;
 
@@ -228,6 +231,19 @@ pub enum SearchError {
 }
 
 pub fn search_from_cache(key: &str, query: &str) -> core::result::Result<Output, SearchError> {
+    search_from_cache_with_options(key, query, &SearchOptions::default())
+}
+
+/**
+ * Searches a registered index, like `search_from_cache`, with additional
+ * options. Legacy (v2) indexes ignore the options.
+ */
+#[allow(unused_variables)]
+pub fn search_from_cache_with_options(
+    key: &str,
+    query: &str,
+    options: &SearchOptions,
+) -> core::result::Result<Output, SearchError> {
     let cache = INDEX_CACHE.lock().unwrap();
     let parsed = match cache.get(key) {
         Some(parsed) => parsed,
@@ -239,21 +255,33 @@ pub fn search_from_cache(key: &str, query: &str) -> core::result::Result<Output,
         ParsedIndex::V2(index) => Ok(V2Search(index, query)),
 
         #[cfg(feature = "search-v3")]
-        ParsedIndex::V3(index) => Ok(V3Search(index, query)),
+        ParsedIndex::V3(index) => Ok(V3Search(index, query, options)),
 
         #[cfg(not(any(feature = "search-v2", feature = "search-v3")))]
         ParsedIndex::Unknown => Err(SearchError::IndexVersionNotSupported),
     }
 }
 
-#[allow(unused_variables)]
 pub fn search(index: Bytes, query: &str) -> core::result::Result<Output, SearchError> {
+    search_with_options(index, query, &SearchOptions::default())
+}
+
+/**
+ * Searches an index, like `search`, with additional options. Legacy (v2)
+ * indexes ignore the options.
+ */
+#[allow(unused_variables)]
+pub fn search_with_options(
+    index: Bytes,
+    query: &str,
+    options: &SearchOptions,
+) -> core::result::Result<Output, SearchError> {
     let index = index_from_bytes(index)?;
 
     #[allow(unreachable_patterns)]
     match index {
         #[cfg(feature = "search-v3")]
-        ParsedIndex::V3(index) => Ok(V3Search(&index, query)),
+        ParsedIndex::V3(index) => Ok(V3Search(&index, query, options)),
 
         #[cfg(feature = "search-v2")]
         ParsedIndex::V2(index) => Ok(V2Search(&index, query)),
@@ -1,5 +1,6 @@
 use crate::Fields;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /**
  * The set of data needed to display search results to a user.
@@ -9,8 +10,25 @@ pub struct Output {
     pub results: Vec<Result>,
     pub total_hit_count: usize,
     pub url_prefix: String,
+
+    /// For each facetable field key, the number of matching entries that
+    /// have each value. Counts include every hit, not only the displayed
+    /// results. Only present if the index was built with facets.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub facets: Option<Facets>,
+
+    /// Corrected versions of the query, most likely first. Only populated
+    /// when the query returns very few results.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub suggestions: Vec<String>,
 }
 
+/**
+ * A map of field keys to a map of that field's values to the number of
+ * entries that have that value.
+ */
+pub type Facets = BTreeMap<String, BTreeMap<String, usize>>;
+
 /**
  * A single document in the list of matches for a search query,
  * along with its display information and excerpts.
@@ -0,0 +1,297 @@
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, convert::TryFrom, fmt};
+
+/**
+ * Options that change how a search is performed, beyond the query itself.
+ */
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(deny_unknown_fields, default)]
+pub struct SearchOptions {
+    /// Restricts results to entries whose fields match. Each key maps to the
+    /// values that key is allowed to have, so an entry is returned if, for
+    /// every key, its field value is one of the listed values. Comparisons
+    /// are case-insensitive.
+    pub filters: HashMap<String, Vec<String>>,
+
+    /// The number of results to skip, for paginating through results.
+    pub offset: usize,
+
+    /// The maximum number of results to return. If not set, the index's
+    /// `displayed_results_count` is used.
+    pub limit: Option<usize>,
+
+    /// If a query word isn't in the index, match words that are within a
+    /// typo or two of it instead.
+    pub fuzzy: bool,
+
+    /// Restricts results to entries whose fields fall within a range, written
+    /// like `"date >= 2024-01-01"` or `"weight < 10"`. Fields are compared as
+    /// the type they were declared with in the config, or as case-insensitive
+    /// text if they weren't declared. Entries without the field are removed.
+    pub range_filters: Vec<RangeFilter>,
+
+    /// How results are ordered, written like `"date desc"`. If not set,
+    /// results are ordered by score, then by title.
+    pub sort: Option<SortOrder>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Comparison {
+    LessThan,
+    LessThanOrEqual,
+    Equal,
+    GreaterThanOrEqual,
+    GreaterThan,
+}
+
+impl Comparison {
+    fn as_str(self) -> &'static str {
+        match self {
+            Comparison::LessThan => "<",
+            Comparison::LessThanOrEqual => "<=",
+            Comparison::Equal => "=",
+            Comparison::GreaterThanOrEqual => ">=",
+            Comparison::GreaterThan => ">",
+        }
+    }
+}
+
+/**
+ * A comparison between one of an entry's fields and a value, like
+ * `date >= 2024-01-01`.
+ */
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(into = "String")]
+#[serde(try_from = "String")]
+pub struct RangeFilter {
+    pub key: String,
+    pub comparison: Comparison,
+    pub value: String,
+}
+
+impl TryFrom<String> for RangeFilter {
+    type Error = String;
+
+    fn try_from(string: String) -> Result<Self, Self::Error> {
+        let error = || {
+            format!(
+                "Unexpected range filter `{string}`, expected something like `date >= 2024-01-01`"
+            )
+        };
+
+        let operator_start = string.find(['<', '>', '=']).ok_or_else(error)?;
+        let (key, rest) = string.split_at(operator_start);
+
+        let comparison = [
+            Comparison::LessThanOrEqual,
+            Comparison::GreaterThanOrEqual,
+            Comparison::LessThan,
+            Comparison::GreaterThan,
+            Comparison::Equal,
+        ]
+        .into_iter()
+        .find(|comparison| rest.starts_with(comparison.as_str()))
+        .ok_or_else(error)?;
+
+        let key = key.trim();
+        let value = rest[comparison.as_str().len()..].trim();
+        if key.is_empty() || value.is_empty() || value.starts_with(['<', '>', '=']) {
+            return Err(error());
+        }
+
+        Ok(RangeFilter {
+            key: key.to_lowercase(),
+            comparison,
+            value: value.to_string(),
+        })
+    }
+}
+
+impl From<RangeFilter> for String {
+    fn from(range_filter: RangeFilter) -> Self {
+        range_filter.to_string()
+    }
+}
+
+impl fmt::Display for RangeFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {}",
+            self.key,
+            self.comparison.as_str(),
+            self.value
+        )
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub enum SortKey {
+    Score,
+    Title,
+    Field(String),
+}
+
+/**
+ * An order for search results, written like `"date desc"`. The key is
+ * `score`, `title`, or the name of a field, and the direction is `asc` or
+ * `desc`. Without a direction, scores are sorted highest first and
+ * everything else is sorted in ascending order.
+ *
+ * Entries without the field always come last. Entries that sort the same are
+ * ordered by score, then by title.
+ */
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(into = "String")]
+#[serde(try_from = "String")]
+pub struct SortOrder {
+    pub key: SortKey,
+    pub descending: bool,
+}
+
+impl TryFrom<String> for SortOrder {
+    type Error = String;
+
+    fn try_from(string: String) -> Result<Self, Self::Error> {
+        let words: Vec<&str> = string.split_whitespace().collect();
+
+        let key = match words.first().map(|word| word.to_lowercase()) {
+            Some(word) if word == "score" => SortKey::Score,
+            Some(word) if word == "title" => SortKey::Title,
+            Some(word) => SortKey::Field(word),
+            None => return Err("The sort order is empty".to_string()),
+        };
+
+        let descending = match words.get(1).map(|word| word.to_lowercase()).as_deref() {
+            Some("asc") => false,
+            Some("desc") => true,
+            None => key == SortKey::Score,
+            Some(_) => {
+                return Err(format!(
+                    "Unexpected sort order `{string}`, expected something like `date desc`"
+                ))
+            }
+        };
+
+        if words.len() > 2 {
+            return Err(format!(
+                "Unexpected sort order `{string}`, expected something like `date desc`"
+            ));
+        }
+
+        Ok(SortOrder { key, descending })
+    }
+}
+
+impl From<SortOrder> for String {
+    fn from(sort_order: SortOrder) -> Self {
+        let key = match sort_order.key {
+            SortKey::Score => "score".to_string(),
+            SortKey::Title => "title".to_string(),
+            SortKey::Field(key) => key,
+        };
+        let direction = if sort_order.descending { "desc" } else { "asc" };
+        format!("{key} {direction}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn search_options_deserialize_from_partial_json() {
+        let computed: SearchOptions =
+            serde_json::from_str(r#"{"filters": {"category": ["guide", "api"]}}"#).unwrap();
+        let expected = SearchOptions {
+            filters: HashMap::from([(
+                "category".to_string(),
+                vec!["guide".to_string(), "api".to_string()],
+            )]),
+            ..SearchOptions::default()
+        };
+        assert_eq!(computed, expected);
+
+        let computed: SearchOptions = serde_json::from_str("{}").unwrap();
+        assert_eq!(computed, SearchOptions::default());
+    }
+
+    #[test]
+    fn search_options_deserialize_pagination() {
+        let computed: SearchOptions =
+            serde_json::from_str(r#"{"offset": 10, "limit": 10}"#).unwrap();
+        let expected = SearchOptions {
+            offset: 10,
+            limit: Some(10),
+            ..SearchOptions::default()
+        };
+        assert_eq!(computed, expected);
+    }
+
+    #[test]
+    fn search_options_deserialize_range_filters_and_sort() {
+        let computed: SearchOptions = serde_json::from_str(
+            r#"{"range_filters": ["Date >= 2024-01-01", "weight<10"], "sort": "date desc"}"#,
+        )
+        .unwrap();
+        let expected = SearchOptions {
+            range_filters: vec![
+                RangeFilter {
+                    key: "date".to_string(),
+                    comparison: Comparison::GreaterThanOrEqual,
+                    value: "2024-01-01".to_string(),
+                },
+                RangeFilter {
+                    key: "weight".to_string(),
+                    comparison: Comparison::LessThan,
+                    value: "10".to_string(),
+                },
+            ],
+            sort: Some(SortOrder {
+                key: SortKey::Field("date".to_string()),
+                descending: true,
+            }),
+            ..SearchOptions::default()
+        };
+        assert_eq!(computed, expected);
+    }
+
+    #[test]
+    fn sort_direction_defaults_depend_on_the_key() {
+        let sort_order = |string: &str| SortOrder::try_from(string.to_string()).unwrap();
+        assert!(sort_order("score").descending);
+        assert!(!sort_order("title").descending);
+        assert!(!sort_order("weight").descending);
+        assert!(!sort_order("score asc").descending);
+    }
+
+    #[test]
+    fn invalid_range_filters_and_sort_orders_fail() {
+        for range_filter in ["date", ">= 2024", "date >=", "date => 2024"] {
+            assert!(
+                RangeFilter::try_from(range_filter.to_string()).is_err(),
+                "{range_filter}"
+            );
+        }
+
+        for sort_order in ["", "date sideways", "date desc please"] {
+            assert!(
+                SortOrder::try_from(sort_order.to_string()).is_err(),
+                "{sort_order}"
+            );
+        }
+    }
+
+    #[test]
+    fn range_filters_and_sort_orders_serialize_as_strings() {
+        let options = SearchOptions {
+            range_filters: vec![RangeFilter::try_from("date>=2024-01-01".to_string()).unwrap()],
+            sort: Some(SortOrder::try_from("date".to_string()).unwrap()),
+            ..SearchOptions::default()
+        };
+        let json = serde_json::to_string(&options).unwrap();
+        assert!(json.contains(r#""range_filters":["date >= 2024-01-01"]"#));
+        assert!(json.contains(r#""sort":"date asc""#));
+    }
+}
@@ -49,6 +49,22 @@ pub fn wasm_search(name: &str, query: &str) -> String {
     WasmOutput::from(result).0
 }
 
+/**
+ * Searches a registered index with options, which are passed as a JSON
+ * string that deserializes into `stork_lib::SearchOptions`.
+ */
+#[wasm_bindgen]
+pub fn wasm_search_with_options(name: &str, query: &str, options: &str) -> String {
+    console_error_panic_hook::set_once();
+    let result = serde_json::from_str::<stork_lib::SearchOptions>(options)
+        .map_err(|e| format!("Could not parse search options: {e}"))
+        .and_then(|options| {
+            stork_lib::search_from_cache_with_options(name, query, &options)
+                .map_err(|e| e.to_string())
+        });
+    WasmOutput::from(result).0
+}
+
 #[wasm_bindgen]
 pub fn wasm_stork_version() -> String {
     env!("CARGO_PKG_VERSION").to_string()